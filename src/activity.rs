@@ -0,0 +1,239 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::VecDeque;
+
+use serde::{de, Deserialize, Deserializer};
+use serde_json::{self, Value};
+
+use error::{Error, Result};
+use pagination::Pages;
+use playlist::Playlist;
+use track::Track;
+
+/// Entry in an activity feed.
+///
+/// Activity types other than uploads and reposts of tracks and playlists are returned as
+/// `Other` with the unparsed `origin`.
+#[derive(Debug, Clone)]
+pub enum Activity {
+    /// A track was uploaded.
+    Track {
+        created_at: String,
+        track: Track,
+    },
+    /// A track was reposted.
+    TrackRepost {
+        created_at: String,
+        track: Track,
+    },
+    /// A playlist was created.
+    Playlist {
+        created_at: String,
+        playlist: Playlist,
+    },
+    /// A playlist was reposted.
+    PlaylistRepost {
+        created_at: String,
+        playlist: Playlist,
+    },
+    /// Any other type of activity.
+    Other {
+        kind: String,
+        created_at: String,
+        origin: Value,
+    },
+}
+
+impl Activity {
+    /// Parses an activity from a feed entry `value`.
+    pub fn from_value(value: Value) -> Result<Activity> {
+        let mut object = match value {
+            Value::Object(object) => object,
+            _ => return Err(Error::ApiError("expected activity to be an object".to_owned())),
+        };
+
+        let kind = match object.get("type").and_then(Value::as_string) {
+            Some(kind) => kind.to_owned(),
+            None => return Err(Error::ApiError("activity is missing a type".to_owned())),
+        };
+
+        let created_at = object.get("created_at")
+            .and_then(Value::as_string)
+            .unwrap_or("")
+            .to_owned();
+
+        let origin = match object.remove("origin") {
+            Some(origin) => origin,
+            None => return Err(Error::ApiError("activity is missing an origin".to_owned())),
+        };
+
+        let activity = match &kind[..] {
+            "track" => Activity::Track {
+                created_at: created_at,
+                track: try!(serde_json::from_value(origin)),
+            },
+            "track-repost" => Activity::TrackRepost {
+                created_at: created_at,
+                track: try!(serde_json::from_value(origin)),
+            },
+            "playlist" => Activity::Playlist {
+                created_at: created_at,
+                playlist: try!(serde_json::from_value(origin)),
+            },
+            "playlist-repost" => Activity::PlaylistRepost {
+                created_at: created_at,
+                playlist: try!(serde_json::from_value(origin)),
+            },
+            _ => Activity::Other {
+                kind: kind,
+                created_at: created_at,
+                origin: origin,
+            },
+        };
+
+        Ok(activity)
+    }
+
+    /// Returns the time of the activity, as an unparsed string.
+    pub fn created_at(&self) -> &str {
+        match *self {
+            Activity::Track { ref created_at, .. } |
+            Activity::TrackRepost { ref created_at, .. } |
+            Activity::Playlist { ref created_at, .. } |
+            Activity::PlaylistRepost { ref created_at, .. } |
+            Activity::Other { ref created_at, .. } => created_at,
+        }
+    }
+
+    /// Returns the track the activity is about, if any.
+    pub fn track(&self) -> Option<&Track> {
+        match *self {
+            Activity::Track { ref track, .. } | Activity::TrackRepost { ref track, .. } =>
+                Some(track),
+            _ => None,
+        }
+    }
+
+    /// Returns the playlist the activity is about, if any.
+    pub fn playlist(&self) -> Option<&Playlist> {
+        match *self {
+            Activity::Playlist { ref playlist, .. } |
+            Activity::PlaylistRepost { ref playlist, .. } => Some(playlist),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the activity is a repost.
+    pub fn is_repost(&self) -> bool {
+        match *self {
+            Activity::TrackRepost { .. } | Activity::PlaylistRepost { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+impl Deserialize for Activity {
+    fn deserialize<D>(deserializer: &mut D) -> ::std::result::Result<Activity, D::Error>
+        where D: Deserializer {
+        let value = try!(Value::deserialize(deserializer));
+
+        Activity::from_value(value).map_err(|error| de::Error::custom(error.to_string()))
+    }
+}
+
+/// Iterator over the activities of a feed, newest first.
+///
+/// Pages are requested as the iterator advances. The `future_href` of the first page is kept as
+/// the cursor to poll for activities newer than the feed with
+/// `MeRequestBuilder::activities_since`.
+#[derive(Debug)]
+pub struct Feed<'a> {
+    pages: Pages<'a, Activity>,
+    current: VecDeque<Activity>,
+    future_href: Option<String>,
+}
+
+impl<'a> Feed<'a> {
+    /// Constructs a feed iterating over the activities on the given `pages`.
+    pub fn new(pages: Pages<'a, Activity>) -> Feed<'a> {
+        Feed {
+            pages: pages,
+            current: VecDeque::new(),
+            future_href: None,
+        }
+    }
+
+    /// Returns the cursor for activities newer than the feed, once the first page is fetched.
+    pub fn future_href(&self) -> Option<&str> {
+        self.future_href.as_ref().map(String::as_str)
+    }
+}
+
+impl<'a> Iterator for Feed<'a> {
+    type Item = Result<Activity>;
+
+    fn next(&mut self) -> Option<Result<Activity>> {
+        loop {
+            if let Some(activity) = self.current.pop_front() {
+                return Some(Ok(activity));
+            }
+
+            match self.pages.next() {
+                Some(Ok(page)) => {
+                    if self.future_href.is_none() {
+                        self.future_href = page.future_href;
+                    }
+
+                    self.current.extend(page.collection);
+                },
+                Some(Err(error)) => return Some(Err(error)),
+                None => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::Value;
+    use super::*;
+    use track::tests::fixture_value;
+
+    fn activity(kind: &str) -> Value {
+        let track = fixture_value();
+        let mut object = BTreeMap::new();
+
+        object.insert("type".to_owned(), Value::String(kind.to_owned()));
+        object.insert("created_at".to_owned(),
+                      Value::String("2016/05/01 12:00:00 +0000".to_owned()));
+        object.insert("origin".to_owned(), track);
+
+        Value::Object(object)
+    }
+
+    #[test]
+    fn test_track_repost() {
+        let activity = Activity::from_value(activity("track-repost")).unwrap();
+
+        assert!(activity.is_repost());
+        assert_eq!(activity.created_at(), "2016/05/01 12:00:00 +0000");
+        assert_eq!(activity.track().map(|track| track.id), Some(262681089));
+    }
+
+    #[test]
+    fn test_unknown_activity() {
+        match Activity::from_value(activity("comment")).unwrap() {
+            Activity::Other { kind, .. } => assert_eq!(kind, "comment"),
+            activity => panic!("unexpected activity: {:?}", activity),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use serde_json;
+
+use client::Client;
+use error::Result;
+use pagination::Pages;
+use track::Track;
+
+/// Registered client application.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct App {
+    /// Integer ID.
+    pub id: usize,
+    /// API resource URL.
+    pub uri: String,
+    /// URL to the SoundCloud.com page
+    pub permalink_url: String,
+    /// URL to an external site.
+    pub external_url: Option<String>,
+    /// Username of the app creator.
+    pub creator: Option<String>,
+    /// Name of the app.
+    pub name: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct SingleAppRequestBuilder<'a> {
+    client: &'a Client,
+    pub id: usize,
+}
+
+impl<'a> SingleAppRequestBuilder<'a> {
+    /// Constructs a new app request.
+    pub fn new(client: &'a Client, id: usize) -> SingleAppRequestBuilder {
+        SingleAppRequestBuilder {
+            client: client,
+            id: id,
+        }
+    }
+
+    /// Sends the request and returns the app.
+    pub fn get(&mut self) -> Result<App> {
+        let no_params: Option<&[(&str, &str)]> = None;
+        let response = try!(self.client.get(&format!("/apps/{}", self.id), no_params));
+        let app: App = try!(serde_json::from_reader(response));
+
+        Ok(app)
+    }
+
+    /// Returns an iterator over the pages of tracks uploaded with the app.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use soundcloud::Client;
+    ///
+    /// let client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+    /// let track = client.track(262681089).get().unwrap();
+    ///
+    /// if let Some(app) = track.created_with {
+    ///     let tracks = client.app(app.id).tracks().next();
+    /// }
+    /// ```
+    pub fn tracks(&self) -> Pages<'a, Track> {
+        Pages::new(self.client, &format!("/apps/{}/tracks", self.id), &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use super::*;
+
+    #[test]
+    fn test_missing_external_url() {
+        let app: App = serde_json::from_str(r#"{
+            "id": 46941,
+            "uri": "https://api.soundcloud.com/apps/46941",
+            "permalink_url": "http://soundcloud.com/apps/soundcloud-for-ios",
+            "creator": "soundcloud"
+        }"#).unwrap();
+
+        assert_eq!(app.external_url, None);
+    }
+}
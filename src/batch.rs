@@ -0,0 +1,381 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Downloading of many tracks at once.
+
+use std::cmp;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use client::{self, Client};
+use error::{Error, Result};
+use template::Template;
+use track::Track;
+
+/// The default number of concurrent download workers.
+pub const DEFAULT_WORKERS: usize = 4;
+
+/// Downloads a list of tracks to a directory with a number of concurrent workers.
+///
+/// Tracks that are downloadable are fetched in their original format with
+/// `Client::download_to_path`, all other tracks are streamed. Streams are written to a file with a
+/// `.part` extension that is renamed once the stream has been received completely. Use the `{ext}`
+/// template field to name files by the format that is actually written.
+///
+/// # Examples
+///
+/// ```no_run
+/// use soundcloud::Client;
+/// use soundcloud::batch::DownloadManager;
+///
+/// let client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+/// let tracks = client.tracks().query(Some("noisia")).get().unwrap().unwrap_or(vec![]);
+/// let report = DownloadManager::new(&client, "downloads").workers(8).run(&tracks);
+///
+/// println!("{} downloaded, {} failed", report.downloaded().count(), report.failed().count());
+/// ```
+#[derive(Debug)]
+pub struct DownloadManager {
+    client: Client,
+    output_dir: PathBuf,
+    template: Template,
+    workers: usize,
+    skip_existing: bool,
+}
+
+/// Result of downloading a single track.
+#[derive(Debug)]
+pub enum Outcome {
+    /// The track was downloaded, with the number of bytes written.
+    Downloaded(usize),
+    /// The file already existed.
+    Skipped,
+    /// The download failed.
+    Failed(Error),
+}
+
+/// Report entry for a single track.
+#[derive(Debug)]
+pub struct Entry {
+    /// ID of the track.
+    pub track_id: u64,
+    /// Path the track was, or would have been, written to.
+    pub path: PathBuf,
+    /// What happened.
+    pub outcome: Outcome,
+}
+
+/// Per-track report of a batch download, in the same order as the tracks were given.
+#[derive(Debug)]
+pub struct Report {
+    /// List of entries.
+    pub entries: Vec<Entry>,
+}
+
+impl Report {
+    /// Returns the entries of tracks that were downloaded.
+    pub fn downloaded<'a>(&'a self) -> Box<Iterator<Item=&'a Entry> + 'a> {
+        Box::new(self.entries.iter().filter(|entry| match entry.outcome {
+            Outcome::Downloaded(_) => true,
+            _ => false,
+        }))
+    }
+
+    /// Returns the entries of tracks that were skipped.
+    pub fn skipped<'a>(&'a self) -> Box<Iterator<Item=&'a Entry> + 'a> {
+        Box::new(self.entries.iter().filter(|entry| match entry.outcome {
+            Outcome::Skipped => true,
+            _ => false,
+        }))
+    }
+
+    /// Returns the entries of tracks that failed to download.
+    pub fn failed<'a>(&'a self) -> Box<Iterator<Item=&'a Entry> + 'a> {
+        Box::new(self.entries.iter().filter(|entry| match entry.outcome {
+            Outcome::Failed(_) => true,
+            _ => false,
+        }))
+    }
+}
+
+impl DownloadManager {
+    /// Constructs a new download manager that writes files to `output_dir`, using the default
+    /// template and number of workers.
+    pub fn new<P: AsRef<Path>>(client: &Client, output_dir: P) -> DownloadManager {
+        DownloadManager {
+            client: client.clone(),
+            output_dir: output_dir.as_ref().to_path_buf(),
+            template: Template::default(),
+            workers: DEFAULT_WORKERS,
+            skip_existing: true,
+        }
+    }
+
+    /// Sets the number of concurrent workers.
+    pub fn workers(&mut self, workers: usize) -> &mut DownloadManager {
+        self.workers = cmp::max(workers, 1);
+        self
+    }
+
+    /// Sets the template used to name the downloaded files.
+    pub fn template(&mut self, template: Template) -> &mut DownloadManager {
+        self.template = template;
+        self
+    }
+
+    /// Sets whether tracks are skipped when their file already exists. Defaults to true.
+    pub fn skip_existing(&mut self, skip_existing: bool) -> &mut DownloadManager {
+        self.skip_existing = skip_existing;
+        self
+    }
+
+    /// Downloads all `tracks` and returns a report of the outcome for each of them.
+    ///
+    /// Tracks whose file names collide within the batch are given unique names.
+    pub fn run(&self, tracks: &[Track]) -> Report {
+        let mut names = self.template.unique_names();
+        let jobs: Vec<(Track, PathBuf)> = tracks.iter()
+            .map(|track| {
+                let name = names.insert(&self.template.render(track));
+                (track.clone(), self.output_dir.join(name))
+            })
+            .collect();
+        let jobs = Arc::new(jobs);
+        let next_index = Arc::new(AtomicUsize::new(0));
+        let (sender, receiver) = mpsc::channel();
+
+        for _ in 0..cmp::min(self.workers, jobs.len()) {
+            let worker = Worker {
+                client: self.client.clone(),
+                skip_existing: self.skip_existing,
+            };
+            let jobs = jobs.clone();
+            let next_index = next_index.clone();
+            let sender = sender.clone();
+
+            thread::spawn(move || {
+                loop {
+                    let index = next_index.fetch_add(1, Ordering::SeqCst);
+
+                    if index >= jobs.len() {
+                        break;
+                    }
+
+                    let (ref track, ref path) = jobs[index];
+                    let entry = worker.run(track, path);
+
+                    if sender.send((index, entry)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(sender);
+
+        let mut entries: Vec<(usize, Entry)> = receiver.iter().collect();
+        entries.sort_by_key(|&(index, _)| index);
+
+        Report {
+            entries: entries.into_iter().map(|(_, entry)| entry).collect(),
+        }
+    }
+}
+
+/// State shared by the downloads of a single worker thread.
+struct Worker {
+    client: Client,
+    skip_existing: bool,
+}
+
+impl Worker {
+    fn run(&self, track: &Track, path: &Path) -> Entry {
+        let outcome = if self.skip_existing && path.exists() {
+            Outcome::Skipped
+        } else {
+            match self.download(track, path) {
+                Ok(len) => Outcome::Downloaded(len),
+                Err(error) => {
+                    warn!("Failed to download track {}: {}", track.id, error);
+                    Outcome::Failed(error)
+                }
+            }
+        };
+
+        Entry {
+            track_id: track.id,
+            path: path.to_path_buf(),
+            outcome: outcome,
+        }
+    }
+
+    fn download(&self, track: &Track, path: &Path) -> Result<usize> {
+        if track.downloadable && track.download_url.is_some() {
+            return self.client.download_to_path(track, path);
+        }
+
+        let part_path = client::partial_path(path);
+        let file = try!(File::create(&part_path));
+
+        match self.client.stream(track, file) {
+            Ok(len) => {
+                try!(fs::rename(&part_path, path));
+                Ok(len)
+            },
+            Err(error) => {
+                let _ = fs::remove_file(&part_path);
+                Err(error)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::{Read, Write};
+    use std::path::{Path, PathBuf};
+
+    use client::Client;
+    use test_support::serve;
+    use template::Template;
+    use track::Track;
+    use track::tests::fixture;
+    use super::*;
+
+    fn track(id: u64) -> Track {
+        let mut track = fixture();
+        track.id = id;
+        track
+    }
+
+    fn output_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("soundcloud-batch-{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn read(path: &Path) -> String {
+        let mut contents = String::new();
+        File::open(path).unwrap().read_to_string(&mut contents).unwrap();
+        contents
+    }
+
+    #[test]
+    fn test_run_reports_each_track_in_order() {
+        let addr = serve(|path, _| {
+            if path.starts_with("/download/1?") {
+                ("200 OK".to_owned(), b"original".to_vec())
+            } else if path.starts_with("/stream/2?") {
+                ("200 OK".to_owned(), b"stream".to_vec())
+            } else {
+                ("404 Not Found".to_owned(), vec![])
+            }
+        });
+        let dir = output_dir("run");
+
+        let mut downloadable = track(1);
+        downloadable.download_url = Some(format!("http://{}/download/1", addr));
+        downloadable.original_content_size = 8;
+
+        let mut streamed = track(2);
+        streamed.downloadable = false;
+        streamed.stream_url = Some(format!("http://{}/stream/2", addr));
+
+        let existing = track(3);
+        File::create(dir.join("3.wav")).unwrap().write_all(b"existing").unwrap();
+
+        let mut missing = track(4);
+        missing.download_url = Some(format!("http://{}/download/4", addr));
+
+        let client = Client::new("abc");
+        let report = DownloadManager::new(&client, &dir)
+            .workers(2)
+            .template(Template::parse("{id}.{ext}").unwrap())
+            .run(&[downloadable, streamed, existing, missing]);
+
+        let ids: Vec<u64> = report.entries.iter().map(|entry| entry.track_id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+
+        match report.entries[0].outcome {
+            Outcome::Downloaded(8) => {},
+            ref outcome => panic!("unexpected outcome: {:?}", outcome),
+        }
+
+        match report.entries[1].outcome {
+            Outcome::Downloaded(6) => {},
+            ref outcome => panic!("unexpected outcome: {:?}", outcome),
+        }
+
+        assert_eq!(report.skipped().map(|entry| entry.track_id).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(report.failed().map(|entry| entry.track_id).collect::<Vec<_>>(), vec![4]);
+
+        assert_eq!(read(&dir.join("1.wav")), "original");
+        assert_eq!(read(&dir.join("2.mp3")), "stream");
+        assert_eq!(read(&dir.join("3.wav")), "existing");
+        assert!(!dir.join("2.mp3.part").exists());
+        assert!(!dir.join("4.wav").exists());
+    }
+
+    #[test]
+    fn test_run_fails_on_missing_stream() {
+        let addr = serve(|_, _| ("404 Not Found".to_owned(), b"not audio".to_vec()));
+        let dir = output_dir("missing-stream");
+
+        let mut streamed = track(1);
+        streamed.downloadable = false;
+        streamed.stream_url = Some(format!("http://{}/stream/1", addr));
+
+        let client = Client::new("abc");
+        let report = DownloadManager::new(&client, &dir)
+            .template(Template::parse("{id}.{ext}").unwrap())
+            .run(&[streamed]);
+
+        match report.entries[0].outcome {
+            Outcome::Failed(_) => {},
+            ref outcome => panic!("unexpected outcome: {:?}", outcome),
+        }
+
+        assert!(!dir.join("1.mp3").exists());
+        assert!(!dir.join("1.mp3.part").exists());
+    }
+
+    #[test]
+    fn test_run_gives_colliding_names_unique_names() {
+        let addr = serve(|path, _| {
+            let body = if path.starts_with("/stream/1?") { "first" } else { "second" };
+
+            ("200 OK".to_owned(), body.as_bytes().to_vec())
+        });
+        let dir = output_dir("collisions");
+        let tracks: Vec<Track> = (1..3)
+            .map(|id| {
+                let mut track = track(id);
+                track.downloadable = false;
+                track.stream_url = Some(format!("http://{}/stream/{}", addr, id));
+                track
+            })
+            .collect();
+
+        let client = Client::new("abc");
+        let report = DownloadManager::new(&client, &dir).run(&tracks);
+
+        assert_eq!(report.downloaded().count(), 2);
+        assert_eq!(report.entries[0].path, dir.join("Isqa - Tree Eater_ Part 1_2.mp3"));
+        assert_eq!(report.entries[1].path, dir.join("Isqa - Tree Eater_ Part 1_2 (1).mp3"));
+        assert_eq!(read(&report.entries[0].path), "first");
+        assert_eq!(read(&report.entries[1].path), "second");
+    }
+}
@@ -0,0 +1,316 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+use std::str;
+
+use url::Url;
+
+use client::{Client, User};
+use error::{Error, Result};
+use media::Media;
+use pagination::{self, DEFAULT_PAGE_SIZE, Page, Pages};
+
+/// The charts endpoint.
+pub const CHARTS_URL: &'static str = "https://api-v2.soundcloud.com/charts";
+
+/// The genre of the charts when none is set.
+pub const DEFAULT_GENRE: &'static str = "all-music";
+
+/// Kind of chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartKind {
+    /// The most played tracks.
+    Top,
+    /// New tracks gaining plays quickly.
+    Trending,
+}
+
+impl ChartKind {
+    /// Returns the name of the chart kind used by the API.
+    pub fn to_str(&self) -> &str {
+        match *self {
+            ChartKind::Top => "top",
+            ChartKind::Trending => "trending",
+        }
+    }
+}
+
+impl str::FromStr for ChartKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ChartKind> {
+        match s {
+            "top" => Ok(ChartKind::Top),
+            "trending" => Ok(ChartKind::Trending),
+            _ => Err(Error::ApiError(format!("unknown chart kind: {}", s))),
+        }
+    }
+}
+
+impl fmt::Display for ChartKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+/// Entry in a chart.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChartEntry {
+    /// Position in the chart, starting at 1.
+    pub rank: usize,
+    /// Chart score, which the entries are ranked by.
+    pub score: f64,
+    /// The charting track.
+    pub track: ChartTrack,
+}
+
+/// Track in a chart.
+///
+/// Charts are served by the v2 API, which leaves out some of the fields of a `Track`. The full
+/// track can be requested with `Client::track`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChartTrack {
+    /// Integer ID.
+    pub id: u64,
+    /// Small representation of the uploaders user.
+    pub user: User,
+    /// Title.
+    pub title: String,
+    /// URL to the SoundCloud.com page.
+    pub permalink_url: String,
+    /// API resource URL.
+    pub uri: String,
+    /// URL to a JPEG image.
+    pub artwork_url: Option<String>,
+    /// Duration in milliseconds.
+    pub duration: u64,
+    /// Genre.
+    pub genre: Option<String>,
+    /// If the track is available for stream.
+    pub streamable: Option<bool>,
+    /// If the track is available for download.
+    pub downloadable: Option<bool>,
+    /// Available transcodings.
+    pub media: Option<Media>,
+    /// Number of playbacks.
+    pub playback_count: Option<u64>,
+    /// Number of likes.
+    pub likes_count: Option<u64>,
+}
+
+/// Entry in a page of a chart as returned by the API, which doesn't include the rank.
+#[derive(Deserialize, Debug)]
+struct RawEntry {
+    score: f64,
+    track: ChartTrack,
+}
+
+/// Iterator over the pages of a chart.
+///
+/// Entries are ranked by their position in the whole chart, so the rank continues across pages.
+#[derive(Debug)]
+pub struct Charts<'a> {
+    pages: Pages<'a, RawEntry>,
+    rank: usize,
+}
+
+impl<'a> Charts<'a> {
+    /// Fetches all remaining pages and returns the entries in a single list.
+    pub fn all(self) -> Result<Vec<ChartEntry>> {
+        pagination::collect_all(self)
+    }
+}
+
+impl<'a> Iterator for Charts<'a> {
+    type Item = Result<Page<ChartEntry>>;
+
+    fn next(&mut self) -> Option<Result<Page<ChartEntry>>> {
+        let page = match self.pages.next() {
+            Some(Ok(page)) => page,
+            Some(Err(error)) => return Some(Err(error)),
+            None => return None,
+        };
+
+        let mut collection = Vec::with_capacity(page.collection.len());
+
+        for entry in page.collection {
+            self.rank += 1;
+            collection.push(ChartEntry {
+                rank: self.rank,
+                score: entry.score,
+                track: entry.track,
+            });
+        }
+
+        Some(Ok(Page {
+            collection: collection,
+            next_href: page.next_href,
+            future_href: page.future_href,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct ChartsRequestBuilder<'a> {
+    client: &'a Client,
+    kind: ChartKind,
+    genre: String,
+    region: Option<String>,
+}
+
+impl<'a> ChartsRequestBuilder<'a> {
+    /// Creates a new charts request builder for the top chart of all genres in all regions.
+    pub fn new(client: &'a Client) -> ChartsRequestBuilder {
+        ChartsRequestBuilder {
+            client: client,
+            kind: ChartKind::Top,
+            genre: DEFAULT_GENRE.to_owned(),
+            region: None,
+        }
+    }
+
+    /// Sets the kind of chart.
+    pub fn kind(&mut self, kind: ChartKind) -> &mut ChartsRequestBuilder<'a> {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets the genre, e.g. `danceedm` or `drumbass`.
+    pub fn genre<S: AsRef<str>>(&mut self, genre: S) -> &mut ChartsRequestBuilder<'a> {
+        self.genre = genre.as_ref().to_owned();
+        self
+    }
+
+    /// Sets the region as a country code, e.g. `US`, or `None` for all regions.
+    pub fn region<S: AsRef<str>>(&mut self, region: Option<S>) -> &mut ChartsRequestBuilder<'a> {
+        self.region = region.map(|s| s.as_ref().to_owned());
+        self
+    }
+
+    /// Returns an iterator over the pages of the chart, with the entries in order of rank.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use soundcloud::{ChartKind, Client};
+    ///
+    /// let client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+    /// let page = client.charts().kind(ChartKind::Trending).genre("drumbass").get().next();
+    ///
+    /// for entry in page.unwrap().unwrap().collection {
+    ///     println!("{}. {} ({})", entry.rank, entry.track.title, entry.score);
+    /// }
+    /// ```
+    pub fn get(&self) -> Charts<'a> {
+        Charts {
+            pages: Pages::from_url(self.client, self.request_url()),
+            rank: 0,
+        }
+    }
+
+    /// Returns the url of the first page of the chart.
+    pub fn request_url(&self) -> Url {
+        let mut url = Url::parse(CHARTS_URL).unwrap();
+
+        url.query_pairs_mut()
+            .append_pair("kind", self.kind.to_str())
+            .append_pair("genre", &urn("soundcloud:genres:", &self.genre))
+            .append_pair("linked_partitioning", "1")
+            .append_pair("limit", &DEFAULT_PAGE_SIZE.to_string());
+
+        if let Some(ref region) = self.region {
+            url.query_pairs_mut().append_pair("region", &urn("soundcloud:regions:", region));
+        }
+
+        url
+    }
+}
+
+/// Returns `name` prefixed with the URN `namespace`, unless it's already a URN.
+fn urn(namespace: &str, name: &str) -> String {
+    if name.starts_with("soundcloud:") {
+        name.to_owned()
+    } else {
+        format!("{}{}", namespace, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{self, Value};
+    use url::Url;
+
+    use super::*;
+    use super::RawEntry;
+    use client::Client;
+    use test_support::{host, serve};
+    use pagination::{Page, Pages};
+
+    const FIXTURE: &'static str = include_str!("../tests/fixtures/charts.json");
+
+    #[test]
+    fn test_parse_chart_page() {
+        let value = serde_json::from_str(FIXTURE).unwrap();
+        let page: Page<RawEntry> = Page::from_value(value).unwrap();
+
+        assert_eq!(page.collection.len(), 2);
+        assert_eq!(page.collection[0].score, 61728.0);
+        assert_eq!(page.collection[0].track.title, "Tree Eater: Part 1/2");
+        assert_eq!(page.collection[0].track.user.username, "Isqa");
+        assert_eq!(page.collection[1].track.artwork_url, None);
+        assert!(page.next_href.is_some());
+    }
+
+    #[test]
+    fn test_rank_continues_across_pages() {
+        let addr = serve(|path, headers| {
+            let host = host(headers);
+            let mut page: Value = serde_json::from_str(FIXTURE).unwrap();
+
+            if let Value::Object(ref mut object) = page {
+                if path.starts_with("/charts?") {
+                    object.insert("next_href".to_owned(),
+                                  Value::String(format!("http://{}/charts/next", host)));
+                } else {
+                    object.remove("next_href");
+                }
+            }
+
+            ("200 OK".to_owned(), serde_json::to_string(&page).unwrap().into_bytes())
+        });
+        let client = Client::new("abc");
+        let url = Url::parse(&format!("http://{}/charts?kind=top", addr)).unwrap();
+        let charts = Charts { pages: Pages::from_url(&client, url), rank: 0 };
+        let ranks: Vec<usize> = charts.all().unwrap().iter().map(|entry| entry.rank).collect();
+
+        assert_eq!(ranks, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_request_url() {
+        let client = Client::new("abc");
+        let url = ChartsRequestBuilder::new(&client)
+            .kind(ChartKind::Trending)
+            .genre("drumbass")
+            .region(Some("GB"))
+            .request_url();
+
+        assert_eq!(url.as_str(),
+                   "https://api-v2.soundcloud.com/charts?kind=trending\
+                    &genre=soundcloud%3Agenres%3Adrumbass&linked_partitioning=1&limit=50\
+                    &region=soundcloud%3Aregions%3AGB");
+    }
+
+    #[test]
+    fn test_kind_from_str() {
+        assert_eq!("top".parse::<ChartKind>().unwrap(), ChartKind::Top);
+        assert!("weekly".parse::<ChartKind>().is_err());
+    }
+}
@@ -9,56 +9,54 @@
 
 use url::Url;
 use hyper;
+use hyper::header::Headers;
+use hyper::method::Method;
+use serde_json::{self, Value};
 
+use std::ffi::OsString;
+use std::fs::{self, OpenOptions};
 use std::result;
 use std::borrow::Borrow;
-use std::io::{self, Write};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
-use track::{Track, TrackRequestBuilder, SingleTrackRequestBuilder};
+use app::SingleAppRequestBuilder;
+use chart::ChartsRequestBuilder;
+use comment::{Comment, SingleCommentRequestBuilder};
+use group::{GroupRequestBuilder, SingleGroupRequestBuilder};
+use hls::{MediaPlaylist, SegmentFetcher};
+use image::{ImageSize, image_url};
+#[cfg(feature = "id3")]
+use id3;
+use media::{self, Preset, Protocol, Transcoding};
+use oembed::{self, OEmbed, OEmbedOptions};
+use pagination::Pages;
+use playlist::{Playlist, SinglePlaylistRequestBuilder};
+use progress::{self, Progress};
+use redirect;
+use stream::{self, TrackStream, SeekableStream};
+use track::{LikedTracks, Track, TrackRequestBuilder, SingleTrackRequestBuilder};
+use user::{MeRequestBuilder, SingleUserRequestBuilder};
+use waveform::{Waveform, waveform_json_url};
 use error::{Error, Result};
 
 pub type Params<'a, K, V> = &'a [(K, V)];
 
-#[derive(Debug)]
-pub struct Client {
-    client_id: String,
-    http_client: hyper::Client,
-}
+/// The number of times a resumable download is attempted before giving up.
+const MAX_DOWNLOAD_ATTEMPTS: usize = 5;
 
-/// Registered client application.
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct App {
-    /// Integer ID.
-    pub id: usize,
-    /// API resource URL.
-    pub uri: String,
-    /// URL to the SoundCloud.com page
-    pub permalink_url: String,
-    /// URL to an external site.
-    pub external_url: String,
-    /// Username of the app creator.
-    pub creator: Option<String>,
-}
+/// The delay before the first retry of a resumable download, which doubles with every attempt.
+const RETRY_DELAY_MS: u64 = 250;
 
-/// User comment.
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct Comment {
-    /// Integer ID.
-    pub id: usize,
-    /// API resource URL.
-    pub uri: String,
-    /// Time of creation, as an unparsed string.
-    pub created_at: String,
-    /// HTML comment body.
-    pub body: String,
-    /// Associated timestamp in milliseconds.
-    pub timestamp: Option<usize>,
-    /// User ID of the commenter.
-    pub user_id: usize,
-    /// Small representation of the commenters user.
-    pub user: User,
-    /// The track ID of the related track.
-    pub track_id: usize,
+#[derive(Debug, Clone)]
+pub struct Client {
+    client_id: String,
+    access_token: Option<String>,
+    http_client: Arc<hyper::Client>,
+    max_redirects: usize,
 }
 
 /// Registered user.
@@ -107,9 +105,76 @@ pub struct User {
     pub followings_count: Option<usize>,
     /// Number of favorited public tracks.
     pub public_favorites_count: Option<usize>,
+    /// Number of private tracks. Only included for the authenticated user.
+    pub private_tracks_count: Option<usize>,
+    /// Number of private playlists. Only included for the authenticated user.
+    pub private_playlists_count: Option<usize>,
+    /// Subscription plan. Only included for the authenticated user.
+    pub plan: Option<String>,
+    /// Upload quota. Only included for the authenticated user.
+    pub quota: Option<Quota>,
+    /// If the primary email address is confirmed. Only included for the authenticated user.
+    pub primary_email_confirmed: Option<bool>,
     // pub avatar_data …
 }
 
+/// Upload quota of the authenticated user.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Quota {
+    /// If the user can upload without limits.
+    pub unlimited_upload_quota: bool,
+    /// Seconds of uploaded audio.
+    pub upload_seconds_used: u64,
+    /// Seconds of audio left to upload, if limited.
+    pub upload_seconds_left: Option<u64>,
+}
+
+/// Connection to an external service, such as Twitter or Facebook.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Connection {
+    /// Integer ID.
+    pub id: usize,
+    /// API resource URL.
+    pub uri: String,
+    /// Time of creation, as an unparsed string.
+    pub created_at: String,
+    /// Name of the account on the service.
+    pub display_name: String,
+    /// Name of the service, e.g. `twitter`.
+    pub service: String,
+    /// Type of the connection.
+    #[serde(rename="type")]
+    pub kind: Option<String>,
+    /// If likes are shared to the service.
+    pub post_favorite: bool,
+    /// If new uploads are shared to the service.
+    pub post_publish: bool,
+}
+
+/// Link to a profile on an external site, such as Instagram or Bandcamp.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WebProfile {
+    /// Integer ID.
+    pub id: usize,
+    /// Time of creation, as an unparsed string.
+    pub created_at: Option<String>,
+    /// Name of the service, e.g. `instagram`, or `personal` for other sites.
+    pub service: String,
+    /// Custom title of the link.
+    pub title: Option<String>,
+    /// URL to the profile.
+    pub url: String,
+    /// Username on the service.
+    pub username: Option<String>,
+}
+
+impl User {
+    /// Returns the url of the users avatar in the given `size`.
+    pub fn avatar_url_for(&self, size: ImageSize) -> String {
+        image_url(&self.avatar_url, size)
+    }
+}
+
 impl Client {
     /// Constructs a new `Client` with the provided `client_id`.
     ///
@@ -126,7 +191,9 @@ impl Client {
 
         Client {
             client_id: client_id.to_owned(),
-            http_client: client,
+            access_token: None,
+            http_client: Arc::new(client),
+            max_redirects: redirect::DEFAULT_MAX_REDIRECTS,
         }
     }
 
@@ -135,6 +202,37 @@ impl Client {
         &self.client_id
     }
 
+    /// Returns the OAuth access token, if the client is authenticated.
+    pub fn access_token(&self) -> Option<&str> {
+        self.access_token.as_ref().map(|token| token.as_str())
+    }
+
+    /// Sets the OAuth access token used to authenticate requests on behalf of a user.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use soundcloud::Client;
+    ///
+    /// let mut client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+    /// client.set_access_token(Some("1-12345-67890-abcdef"));
+    ///
+    /// assert_eq!(client.access_token(), Some("1-12345-67890-abcdef"));
+    /// ```
+    pub fn set_access_token(&mut self, access_token: Option<&str>) {
+        self.access_token = access_token.map(str::to_owned);
+    }
+
+    /// Returns the maximum number of redirects followed when downloading or streaming.
+    pub fn max_redirects(&self) -> usize {
+        self.max_redirects
+    }
+
+    /// Sets the maximum number of redirects followed when downloading or streaming.
+    pub fn set_max_redirects(&mut self, max_redirects: usize) {
+        self.max_redirects = max_redirects;
+    }
+
     /// Creates and sends a HTTP GET request to the API endpoint.
     ///
     /// A `client_id` parameter will automatically be added to the request.
@@ -169,62 +267,501 @@ impl Client {
             }
         }
 
-        let response = self.http_client.get(url).send();
+        let response = self.http_client.get(url).headers(self.default_headers()).send();
         response
     }
 
-    pub fn download<W: Write>(&self, track: &Track, mut writer: W) -> Result<usize> {
-        use hyper::header::Location;
+    /// Creates and sends an authenticated HTTP request with the given `method` to the API
+    /// endpoint at `path`.
+    ///
+    /// The `params` are sent as a form in the body of `POST` and `PUT` requests and in the query
+    /// string otherwise.
+    ///
+    /// Returns the HTTP response if it was successful, an error otherwise.
+    fn send(&self, method: Method, path: &str, params: &[(&str, String)])
+        -> Result<hyper::client::Response> {
+        use hyper::header::ContentType;
+        use url::form_urlencoded;
+
+        if self.access_token.is_none() {
+            return Err(Error::NotAuthenticated);
+        }
+
+        let mut url = self.api_url(path);
+        let response = match method {
+            Method::Post | Method::Put => {
+                let body = form_urlencoded::Serializer::new(String::new())
+                    .extend_pairs(params)
+                    .finish();
+
+                try!(self.http_client.request(method, url)
+                     .headers(self.default_headers())
+                     .header(ContentType::form_url_encoded())
+                     .body(&body[..])
+                     .send())
+            },
+            _ => {
+                url.query_pairs_mut().extend_pairs(params);
+
+                try!(self.http_client.request(method, url).headers(self.default_headers()).send())
+            }
+        };
+
+        check_response(response)
+    }
+
+    /// Returns the url of the API endpoint at `path` with the `client_id` parameter set.
+    fn api_url(&self, path: &str) -> Url {
+        self.parse_url(format!("https://{}{}", super::API_HOST, path))
+    }
+
+    /// Sends a HTTP GET request to the API `url` and returns the parsed JSON response.
+    ///
+    /// A `client_id` parameter is added to the url unless it's already present, and the request
+    /// is authenticated if the client has an access token.
+    fn get_json(&self, mut url: Url) -> Result<Value> {
+        if !url.query_pairs().any(|(key, _)| key == "client_id") {
+            url.query_pairs_mut().append_pair("client_id", &self.client_id);
+        }
+
+        let response = try!(check_response(try!(self.get_url(url, self.default_headers()))));
+        let value: Value = try!(serde_json::from_reader(response));
+
+        Ok(value)
+    }
+
+    /// Downloads the tracks original file to the `writer` if the track is downloadable.
+    ///
+    /// Returns the number of bytes written.
+    pub fn download<W: Write>(&self, track: &Track, writer: W) -> Result<usize> {
+        self.download_with_progress(track, writer, |_| {})
+    }
 
+    /// Downloads the tracks original file to the `writer` like `download`, calling `observer`
+    /// with the current `Progress` as data is received.
+    ///
+    /// The total size is taken from the `Content-Length` of the response, falling back to the
+    /// tracks `original_content_size`.
+    pub fn download_with_progress<W, F>(&self, track: &Track, mut writer: W, observer: F)
+        -> Result<usize> where W: Write, F: FnMut(&Progress) {
         if !track.downloadable || !track.download_url.is_some() {
             return Err(Error::TrackNotDownloadable);
         }
 
         let url = self.parse_url(track.download_url.as_ref().unwrap());
-        let mut response = try!(self.http_client.get(url).send());
+        let mut response = try!(check_response(try!(self.get_url(url, Headers::new()))));
+        let total = content_length(&response).or(original_size(track));
+
+        try!(progress::copy(&mut response, &mut writer, total, observer).map(|n| Ok(n as usize)))
+    }
+
+    /// Downloads the tracks original file to `path`, resuming a previously interrupted download
+    /// if one exists.
+    ///
+    /// The data is written to a file with a `.part` extension next to `path`. If the transfer is
+    /// interrupted it is resumed from the end of the partial file using a HTTP `Range` request.
+    /// Once the partial file matches the tracks `original_content_size` it is renamed to `path`.
+    /// A partial file larger than that is discarded.
+    ///
+    /// Failed requests, server errors and interrupted transfers are retried with an exponential
+    /// backoff, up to a maximum number of attempts. After the last attempt the partial file is
+    /// kept, so that a later call resumes from it. Client errors like `404 Not Found` aren't
+    /// retried and remove the partial file.
+    ///
+    /// Returns the size of the downloaded file on success.
+    pub fn download_to_path<P: AsRef<Path>>(&self, track: &Track, path: P) -> Result<usize> {
+        use hyper::header::{Range, ByteRangeSpec};
+        use hyper::status::StatusCode;
+
+        if !track.downloadable || !track.download_url.is_some() {
+            return Err(Error::TrackNotDownloadable);
+        }
+
+        let path = path.as_ref();
+        let part_path = partial_path(path);
+        // If the size is unknown the download is complete once the body has been read without
+        // errors.
+        let expected_size = original_size(track);
+        let url = self.parse_url(track.download_url.as_ref().unwrap());
+        let mut attempts = 0;
+
+        loop {
+            let mut file = try!(OpenOptions::new().create(true).append(true).open(&part_path));
+            let offset = try!(file.metadata()).len();
+
+            let offset = match expected_size {
+                Some(expected_size) if offset == expected_size => break,
+                Some(expected_size) if offset > expected_size => {
+                    warn!("Partial download of track {} is larger than expected, restarting",
+                          track.id);
+                    try!(file.set_len(0));
+                    0
+                },
+                _ => offset,
+            };
+
+            attempts += 1;
+
+            let mut headers = Headers::new();
+
+            if offset > 0 {
+                headers.set(Range::Bytes(vec![ByteRangeSpec::AllFrom(offset)]));
+            }
+
+            let mut response = match self.get_url(url.clone(), headers) {
+                Ok(response) => response,
+                Err(error @ Error::HttpError(_)) | Err(error @ Error::Io(_)) => {
+                    if attempts >= MAX_DOWNLOAD_ATTEMPTS {
+                        return Err(error);
+                    }
+
+                    warn!("Request for track {} failed: {}, retrying", track.id, error);
+                    thread::sleep(retry_delay(attempts));
+                    continue;
+                },
+                Err(error) => return Err(error),
+            };
+
+            match response.status {
+                StatusCode::PartialContent => {},
+                // The server ignored our range request and sent the whole body - start over.
+                StatusCode::Ok => try!(file.set_len(0)),
+                // There's nothing left to fetch.
+                StatusCode::RangeNotSatisfiable => break,
+                status if status.is_server_error() && attempts < MAX_DOWNLOAD_ATTEMPTS => {
+                    warn!("Request for track {} failed: {}, retrying", track.id, status);
+                    thread::sleep(retry_delay(attempts));
+                    continue;
+                },
+                status => {
+                    if !status.is_server_error() {
+                        drop(file);
+                        let _ = fs::remove_file(&part_path);
+                    }
+
+                    return Err(Error::ApiError(format!("unexpected status: {}", status)));
+                },
+            }
+
+            if let Err(error) = io::copy(&mut response, &mut file) {
+                if attempts >= MAX_DOWNLOAD_ATTEMPTS {
+                    return Err(Error::Io(error));
+                }
 
-        // Follow the redirect just this once.
-        if let Some(header) = response.headers.get::<Location>().cloned() {
-            let url = Url::parse(&header).unwrap();
-            response = try!(self.http_client.get(url).send());
+                warn!("Download of track {} interrupted: {}, resuming", track.id, error);
+                thread::sleep(retry_delay(attempts));
+            } else if expected_size.is_none() || attempts >= MAX_DOWNLOAD_ATTEMPTS {
+                break;
+            }
+        }
+
+        let size = try!(fs::metadata(&part_path)).len();
+
+        if let Some(expected_size) = expected_size {
+            if size != expected_size {
+                return Err(Error::SizeMismatch(expected_size, size));
+            }
         }
 
-        try!(io::copy(&mut response, &mut writer).map(|n| Ok(n as usize)))
+        try!(fs::rename(&part_path, path));
+
+        Ok(size as usize)
     }
 
     /// Starts streaming the track provided in the tracks `stream_url` to the `writer` if the track
     /// is streamable via the API.
-    pub fn stream<W: Write>(&self, track: &Track, mut writer: W) -> Result<usize> {
-        use hyper::header::Location;
+    ///
+    /// Tracks without a `stream_url` are streamed from their transcodings instead, using the
+    /// default presets.
+    pub fn stream<W: Write>(&self, track: &Track, writer: W) -> Result<usize> {
+        if track.stream_url.is_none() && track.media.is_some() {
+            return self.stream_transcoding(track, media::DEFAULT_PRESETS, writer);
+        }
+
+        self.stream_with_progress(track, writer, |_| {})
+    }
+
+    /// Streams the first of the tracks transcodings that matches one of the `presets` to the
+    /// `writer`.
+    ///
+    /// Progressive transcodings are copied as-is, while the segments of HLS transcodings are
+    /// concatenated in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use soundcloud::{Client, Preset};
+    ///
+    /// let client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+    /// let track = client.track(262681089).get().unwrap();
+    /// let mut buffer = vec![];
+    ///
+    /// client.stream_transcoding(&track, &[Preset::HlsMp3], &mut buffer).unwrap();
+    /// ```
+    pub fn stream_transcoding<W: Write>(&self, track: &Track, presets: &[Preset], mut writer: W)
+        -> Result<usize> {
+        let transcoding = match track.media.as_ref()
+            .and_then(|media| media.preferred_transcoding(presets)) {
+            Some(transcoding) => transcoding,
+            None => return Err(Error::TrackNotStreamable),
+        };
+
+        let url = try!(self.resolve_transcoding(transcoding));
+
+        match try!(transcoding.protocol()) {
+            Protocol::Progressive => {
+                let mut response = try!(check_response(try!(self.get_url(url, Headers::new()))));
+
+                try!(io::copy(&mut response, &mut writer).map(|n| Ok(n as usize)))
+            },
+            Protocol::Hls => {
+                let mut input = String::new();
 
-        if !track.streamable || !track.stream_url.is_some() {
-            return Err(Error::TrackNotStreamable);
+                let mut response = try!(check_response(try!(self.get_url(url.clone(),
+                                                                         Headers::new()))));
+
+                try!(response.read_to_string(&mut input));
+
+                let playlist = try!(MediaPlaylist::parse(&input, &url));
+
+                SegmentFetcher::new(self).fetch(&playlist, writer)
+            },
         }
+    }
 
-        let url = self.parse_url(track.stream_url.as_ref().unwrap());
-        let mut response = try!(self.http_client.get(url).send());
+    /// Resolves the signed media url of a `transcoding`.
+    pub fn resolve_transcoding(&self, transcoding: &Transcoding) -> Result<Url> {
+        let url = self.parse_url(&transcoding.url);
+        let response = try!(self.get_url(url, Headers::new()));
+        let body: Value = try!(serde_json::from_reader(response));
+
+        match body.find("url").and_then(Value::as_string) {
+            Some(url) => Url::parse(url)
+                .map_err(|_| Error::ApiError(format!("invalid media url: {}", url))),
+            None => Err(Error::ApiError("expected media url in response".to_owned())),
+        }
+    }
+
+    /// Streams the track to the `writer` like `stream`, calling `observer` with the current
+    /// `Progress` as data is received.
+    ///
+    /// Tracks without a `stream_url` are streamed from a progressive transcoding. The total size
+    /// is taken from the `Content-Length` of the response, if present.
+    pub fn stream_with_progress<W, F>(&self, track: &Track, mut writer: W, observer: F)
+        -> Result<usize> where W: Write, F: FnMut(&Progress) {
+        let mut stream = try!(self.open_stream(track));
+        let total = stream.content_length();
+
+        try!(progress::copy(&mut stream, &mut writer, total, observer).map(|n| Ok(n as usize)))
+    }
+
+    /// Opens the tracks audio stream and returns a reader over it, if the track is streamable via
+    /// the API or has a progressive transcoding.
+    ///
+    /// Unlike `stream` this doesn't block until the whole track has been received, so the audio
+    /// can be fed to a decoder as it arrives and the stream can be dropped early.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Read;
+    /// use soundcloud::Client;
+    ///
+    /// let client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+    /// let track = client.track(262681089).get().unwrap();
+    /// let mut stream = client.open_stream(&track).unwrap();
+    /// let mut header = [0; 3];
+    ///
+    /// stream.read_exact(&mut header).unwrap();
+    /// ```
+    pub fn open_stream(&self, track: &Track) -> Result<TrackStream> {
+        let url = try!(self.progressive_stream_url(track));
+        let response = try!(check_response(try!(self.get_url(url, Headers::new()))));
+
+        Ok(TrackStream::new(response))
+    }
 
-        // Follow the redirect just this once.
-        if let Some(header) = response.headers.get::<Location>().cloned() {
-            let url = Url::parse(&header).unwrap();
-            response = try!(self.http_client.get(url).send());
+    /// Opens the tracks audio stream as a reader that supports seeking, if the track is
+    /// streamable via the API or has a progressive transcoding.
+    ///
+    /// Data is fetched on demand with HTTP `Range` requests, and seeking outside of the read-ahead
+    /// buffer reconnects at the new position.
+    pub fn open_seekable_stream(&self, track: &Track) -> Result<SeekableStream> {
+        let url = try!(self.progressive_stream_url(track));
+
+        Ok(stream::seekable_stream(self, url))
+    }
+
+    /// Returns the url of a progressive stream of the track, which is its `stream_url` if the
+    /// track is streamable via the API, or otherwise the url of a progressive MP3 transcoding.
+    fn progressive_stream_url(&self, track: &Track) -> Result<Url> {
+        if track.streamable {
+            if let Some(ref stream_url) = track.stream_url {
+                return Ok(self.parse_url(stream_url));
+            }
+        }
+
+        match track.media.as_ref()
+            .and_then(|media| media.preferred_transcoding(&[Preset::ProgressiveMp3])) {
+            Some(transcoding) => self.resolve_transcoding(transcoding),
+            None => Err(Error::TrackNotStreamable),
+        }
+    }
+
+    /// Downloads the image at `url`, an artwork or avatar url, in the given `size` to the
+    /// `writer`.
+    ///
+    /// If the image isn't available in the requested size, the next smaller size is tried until
+    /// one is found.
+    ///
+    /// Returns the number of bytes written and the MIME type of the image from the
+    /// `Content-Type` of the response, e.g. `image/jpeg`, if the server sent one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use soundcloud::{Client, ImageSize};
+    ///
+    /// let client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+    /// let track = client.track(262681089).get().unwrap();
+    /// let mut artwork = vec![];
+    ///
+    /// client.fetch_image(&track.artwork_url.unwrap(), ImageSize::T500x500, &mut artwork).unwrap();
+    /// ```
+    pub fn fetch_image<W: Write>(&self, url: &str, size: ImageSize, mut writer: W)
+        -> Result<(usize, Option<String>)> {
+        use hyper::header::ContentType;
+        use hyper::mime::Mime;
+        use hyper::status::StatusCode;
+
+        let mut tried = vec![];
+
+        for &size in size.fallbacks() {
+            let sized_url = image_url(url, size);
+
+            if tried.contains(&sized_url) {
+                continue;
+            }
+
+            let parsed_url = try!(Url::parse(&sized_url)
+                .map_err(|_| Error::ApiError(format!("invalid image url: {}", sized_url))));
+            let mut response = try!(self.get_url(parsed_url, Headers::new()));
+
+            match response.status {
+                StatusCode::NotFound | StatusCode::Forbidden => {
+                    debug!("Image {} is not available", sized_url);
+                    tried.push(sized_url);
+                },
+                status if status.is_success() => {
+                    let mime_type = response.headers.get::<ContentType>()
+                        .map(|&ContentType(Mime(ref top, ref sub, _))| format!("{}/{}", top, sub));
+                    let len = try!(io::copy(&mut response, &mut writer));
+
+                    return Ok((len as usize, mime_type));
+                },
+                status => return Err(Error::ApiError(format!("unexpected status: {}", status))),
+            }
+        }
+
+        Err(Error::ImageNotFound(url.to_owned()))
+    }
+
+    /// Fetches the waveform data of the `track`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use soundcloud::Client;
+    ///
+    /// let client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+    /// let track = client.track(262681089).get().unwrap();
+    /// let waveform = client.waveform(&track).unwrap();
+    ///
+    /// println!("{}", waveform.render_blocks(80));
+    /// ```
+    pub fn waveform(&self, track: &Track) -> Result<Waveform> {
+        let json_url = waveform_json_url(&track.waveform_url);
+
+        let url = match json_url.and_then(|url| Url::parse(&url).ok()) {
+            Some(url) => url,
+            None => return Err(Error::ApiError(format!("unsupported waveform url: {}",
+                                                       track.waveform_url))),
+        };
+
+        let response = try!(self.get_url(url, Headers::new()));
+        let waveform: Waveform = try!(serde_json::from_reader(response));
+
+        Ok(waveform)
+    }
+
+    /// Writes ID3v2 tags with the metadata of the `track` to the MP3 file at `path`, embedding the
+    /// tracks artwork if it has any.
+    ///
+    /// This is only available with the `id3` feature enabled.
+    #[cfg(feature = "id3")]
+    pub fn write_tags<P: AsRef<Path>>(&self, track: &Track, path: P) -> Result<()> {
+        let mut tag = id3::Tag::from_track(track);
+
+        if let Some(ref artwork_url) = track.artwork_url {
+            let mut data = vec![];
+
+            match self.fetch_image(artwork_url, ImageSize::T500x500, &mut data) {
+                Ok((_, Some(mime_type))) => tag.set_artwork(&mime_type, data),
+                // Artwork is served as JPEG unless it was uploaded in another format.
+                Ok((_, None)) => tag.set_artwork("image/jpeg", data),
+                Err(Error::ImageNotFound(_)) => {},
+                Err(error) => return Err(error),
+            }
         }
 
-        try!(io::copy(&mut response, &mut writer).map(|n| Ok(n as usize)))
+        tag.write_to_path(path)
     }
 
     /// Resolves any soundcloud resource and returns it as a `Url`.
+    ///
+    /// The returned url is the first redirect hop of the `/resolve` endpoint, which isn't
+    /// followed.
     pub fn resolve(&self, url: &str) -> Result<Url> {
         use hyper::header::Location;
         let response = try!(self.get("/resolve", Some(&[("url", url)])));
 
         if let Some(header) = response.headers.get::<Location>() {
-            Ok(Url::parse(header).unwrap())
+            let base = Url::parse(&format!("https://{}/resolve", super::API_HOST)).unwrap();
+            redirect::resolve_location(&base, header)
         } else {
             Err(Error::ApiError("expected location header".to_owned()))
         }
     }
 
+    /// Requests the oEmbed representation of the SoundCloud `url`, e.g. a track or playlist page,
+    /// with the HTML snippet of an embedded player.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use soundcloud::{Client, OEmbedOptions};
+    ///
+    /// let client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+    /// let mut options = OEmbedOptions::new();
+    /// options.maxheight(166).show_comments(false);
+    ///
+    /// let oembed = client.oembed("https://soundcloud.com/isqa/tree-eater-part-1", &options);
+    /// println!("{}", oembed.unwrap().html);
+    /// ```
+    pub fn oembed(&self, url: &str, options: &OEmbedOptions) -> Result<OEmbed> {
+        let mut request_url = Url::parse(oembed::OEMBED_URL).unwrap();
+
+        request_url.query_pairs_mut()
+            .append_pair("format", "json")
+            .append_pair("url", url)
+            .extend_pairs(options.params());
+
+        let response = try!(check_response(try!(self.get_url(request_url, Headers::new()))));
+        let value: Value = try!(serde_json::from_reader(response));
+
+        OEmbed::from_value(value)
+    }
+
     /// Returns a builder for a single track-by-id request.
     ///
     /// # Examples
@@ -241,6 +778,210 @@ impl Client {
         SingleTrackRequestBuilder::new(self, id)
     }
 
+    /// Returns a builder for the top and trending track charts.
+    pub fn charts(&self) -> ChartsRequestBuilder {
+        ChartsRequestBuilder::new(self)
+    }
+
+    /// Returns a builder for a single comment-by-id request.
+    pub fn comment(&self, id: usize) -> SingleCommentRequestBuilder {
+        SingleCommentRequestBuilder::new(self, id)
+    }
+
+    /// Posts a comment with the given `body` on the `track`, optionally at a `timestamp` in
+    /// milliseconds into the track.
+    ///
+    /// This requires the client to be authenticated.
+    pub fn post_comment(&self, track: &Track, body: &str, timestamp: Option<u64>)
+        -> Result<Comment> {
+        let mut params = vec![("comment[body]", body.to_owned())];
+
+        if let Some(timestamp) = timestamp {
+            params.push(("comment[timestamp]", timestamp.to_string()));
+        }
+
+        let path = format!("/tracks/{}/comments", track.id);
+        let response = try!(self.send(Method::Post, &path, &params));
+        let comment: Comment = try!(serde_json::from_reader(response));
+
+        Ok(comment)
+    }
+
+    /// Deletes the `comment`.
+    ///
+    /// This requires the client to be authenticated as either the commenter or the owner of the
+    /// track.
+    pub fn delete_comment(&self, comment: &Comment) -> Result<()> {
+        let path = format!("/tracks/{}/comments/{}", comment.track_id, comment.id);
+
+        try!(self.send(Method::Delete, &path, &[]));
+
+        Ok(())
+    }
+
+    /// Returns a builder for a single app-by-id request.
+    pub fn app(&self, id: usize) -> SingleAppRequestBuilder {
+        SingleAppRequestBuilder::new(self, id)
+    }
+
+    /// Returns a builder for a single group-by-id request.
+    pub fn group(&self, id: usize) -> SingleGroupRequestBuilder {
+        SingleGroupRequestBuilder::new(self, id)
+    }
+
+    /// Returns a builder for searching groups.
+    pub fn groups(&self) -> GroupRequestBuilder {
+        GroupRequestBuilder::new(self)
+    }
+
+    /// Returns a builder for a single playlist-by-id request.
+    pub fn playlist(&self, id: u64) -> SinglePlaylistRequestBuilder {
+        SinglePlaylistRequestBuilder::new(self, id)
+    }
+
+    /// Returns an iterator over the pages of tracks liked by the authenticated user.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use soundcloud::Client;
+    ///
+    /// let mut client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+    /// client.set_access_token(Some(env!("SOUNDCLOUD_ACCESS_TOKEN")));
+    ///
+    /// for track in client.liked_tracks().all().unwrap() {
+    ///     println!("{}", track.title);
+    /// }
+    /// ```
+    pub fn liked_tracks(&self) -> LikedTracks {
+        LikedTracks::new(self)
+    }
+
+    /// Returns an iterator over the pages of playlists liked by the authenticated user.
+    pub fn liked_playlists(&self) -> Pages<Playlist> {
+        Pages::new(self, "/me/likes/playlists", &[])
+    }
+
+    /// Likes the `track` as the authenticated user.
+    pub fn like_track(&self, track: &Track) -> Result<()> {
+        try!(self.send(Method::Post, &format!("/likes/tracks/{}", track.id), &[]));
+
+        Ok(())
+    }
+
+    /// Removes the authenticated users like of the `track`.
+    pub fn unlike_track(&self, track: &Track) -> Result<()> {
+        try!(self.send(Method::Delete, &format!("/likes/tracks/{}", track.id), &[]));
+
+        Ok(())
+    }
+
+    /// Likes the `playlist` as the authenticated user.
+    pub fn like_playlist(&self, playlist: &Playlist) -> Result<()> {
+        try!(self.send(Method::Post, &format!("/likes/playlists/{}", playlist.id), &[]));
+
+        Ok(())
+    }
+
+    /// Removes the authenticated users like of the `playlist`.
+    pub fn unlike_playlist(&self, playlist: &Playlist) -> Result<()> {
+        try!(self.send(Method::Delete, &format!("/likes/playlists/{}", playlist.id), &[]));
+
+        Ok(())
+    }
+
+    /// Reposts the `track` as the authenticated user.
+    pub fn repost_track(&self, track: &Track) -> Result<()> {
+        try!(self.send(Method::Post, &format!("/reposts/tracks/{}", track.id), &[]));
+
+        Ok(())
+    }
+
+    /// Removes the authenticated users repost of the `track`.
+    pub fn unrepost_track(&self, track: &Track) -> Result<()> {
+        try!(self.send(Method::Delete, &format!("/reposts/tracks/{}", track.id), &[]));
+
+        Ok(())
+    }
+
+    /// Reposts the `playlist` as the authenticated user.
+    pub fn repost_playlist(&self, playlist: &Playlist) -> Result<()> {
+        try!(self.send(Method::Post, &format!("/reposts/playlists/{}", playlist.id), &[]));
+
+        Ok(())
+    }
+
+    /// Removes the authenticated users repost of the `playlist`.
+    pub fn unrepost_playlist(&self, playlist: &Playlist) -> Result<()> {
+        try!(self.send(Method::Delete, &format!("/reposts/playlists/{}", playlist.id), &[]));
+
+        Ok(())
+    }
+
+    /// Checks whether the authenticated user likes the `track` and updates its `user_favorite`
+    /// field accordingly.
+    pub fn is_liked(&self, track: &mut Track) -> Result<bool> {
+        let url = self.api_url(&format!("/me/likes/tracks/{}", track.id));
+        let liked = try!(self.like_exists(url));
+
+        track.user_favorite = Some(liked);
+
+        Ok(liked)
+    }
+
+    /// Returns true if the like resource at `url` exists, or false if the API responds with
+    /// `404 Not Found`.
+    fn like_exists(&self, url: Url) -> Result<bool> {
+        use hyper::status::StatusCode;
+
+        if self.access_token.is_none() {
+            return Err(Error::NotAuthenticated);
+        }
+
+        let response = try!(self.get_url(url, self.default_headers()));
+
+        match response.status {
+            StatusCode::NotFound => Ok(false),
+            _ => Ok(try!(check_response(response)).status.is_success()),
+        }
+    }
+
+    /// Returns a builder for a single user-by-id request.
+    pub fn user(&self, id: usize) -> SingleUserRequestBuilder {
+        SingleUserRequestBuilder::new(self, id)
+    }
+
+    /// Returns a builder for requests about the authenticated user.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use soundcloud::Client;
+    ///
+    /// let mut client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+    /// client.set_access_token(Some(env!("SOUNDCLOUD_ACCESS_TOKEN")));
+    ///
+    /// let me = client.me().get().unwrap();
+    /// println!("authenticated as {}", me.username);
+    /// ```
+    pub fn me(&self) -> MeRequestBuilder {
+        MeRequestBuilder::new(self)
+    }
+
+    /// Follows the `user` as the authenticated user.
+    pub fn follow(&self, user: &User) -> Result<()> {
+        try!(self.send(Method::Put, &format!("/me/followings/{}", user.id), &[]));
+
+        Ok(())
+    }
+
+    /// Stops following the `user` as the authenticated user.
+    pub fn unfollow(&self, user: &User) -> Result<()> {
+        try!(self.send(Method::Delete, &format!("/me/followings/{}", user.id), &[]));
+
+        Ok(())
+    }
+
     /// Returns a builder for searching tracks with multiple criteria.
     ///
     /// # Examples
@@ -263,6 +1004,100 @@ impl Client {
         url.query_pairs_mut().append_pair("client_id", &self.client_id);
         url
     }
+
+    /// Returns the headers sent with every API request.
+    fn default_headers(&self) -> Headers {
+        use hyper::header::Authorization;
+
+        let mut headers = Headers::new();
+
+        if let Some(ref access_token) = self.access_token {
+            headers.set(Authorization(format!("OAuth {}", access_token)));
+        }
+
+        headers
+    }
+
+    /// Sends a HTTP GET request with the given `headers` to an absolute `url`, following up to
+    /// `max_redirects` redirects.
+    ///
+    /// Unlike `get` no `client_id` parameter is added to the request.
+    fn get_url(&self, url: Url, headers: Headers) -> Result<hyper::client::Response> {
+        redirect::follow(&self.http_client, url, headers, self.max_redirects)
+    }
+}
+
+/// Sends a HTTP GET request with `client`, like `Client::get_url`.
+///
+/// This is only visible within the crate, since the `client` module itself is private.
+pub fn get_url(client: &Client, url: Url, headers: Headers) -> Result<hyper::client::Response> {
+    client.get_url(url, headers)
+}
+
+/// Returns the url of the API endpoint at `path` for `client`, like `Client::api_url`.
+///
+/// This is only visible within the crate, since the `client` module itself is private.
+pub fn api_url(client: &Client, path: &str) -> Url {
+    client.api_url(path)
+}
+
+/// Sends a HTTP GET request with `client` and returns the parsed JSON response, like
+/// `Client::get_json`.
+///
+/// This is only visible within the crate, since the `client` module itself is private.
+pub fn get_json(client: &Client, url: Url) -> Result<Value> {
+    client.get_json(url)
+}
+
+/// Returns the `response` if its status is successful, or an error with the message from the
+/// response body otherwise.
+fn check_response(mut response: hyper::client::Response) -> Result<hyper::client::Response> {
+    if response.status.is_success() {
+        return Ok(response);
+    }
+
+    let status = response.status;
+    let body: Option<Value> = serde_json::from_reader(&mut response).ok();
+    let message = body.and_then(|body| {
+        body.find("errors")
+            .and_then(Value::as_array)
+            .and_then(|errors| errors.first())
+            .and_then(|error| error.find("error_message"))
+            .and_then(Value::as_string)
+            .map(str::to_owned)
+    });
+
+    Err(Error::ApiError(message.unwrap_or(status.to_string())))
+}
+
+/// Returns the value of the `Content-Length` header of the `response`, if present.
+fn content_length(response: &hyper::client::Response) -> Option<u64> {
+    use hyper::header::ContentLength;
+
+    response.headers.get::<ContentLength>().map(|&ContentLength(len)| len)
+}
+
+/// Returns the delay before retrying a download that failed on the given attempt, starting at 1.
+fn retry_delay(attempts: usize) -> Duration {
+    Duration::from_millis(RETRY_DELAY_MS << (attempts - 1))
+}
+
+/// Returns the size of the tracks original file, or `None` if the API reports a size of 0
+/// because it doesn't know it.
+fn original_size(track: &Track) -> Option<u64> {
+    match track.original_content_size {
+        0 => None,
+        size => Some(size),
+    }
+}
+
+/// Returns the path of the partial file used while downloading to `path`.
+///
+/// This is only visible within the crate, since the `client` module itself is private.
+pub fn partial_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().map(|s| s.to_os_string()).unwrap_or(OsString::new());
+    file_name.push(".part");
+    path.with_file_name(file_name)
 }
 
 #[cfg(test)]
@@ -274,6 +1109,143 @@ mod tests {
         Client::new(env!("SOUNDCLOUD_CLIENT_ID"))
     }
 
+    #[test]
+    fn test_like_exists() {
+        use test_support::serve;
+
+        let addr = serve(|path, headers| {
+            if !headers.iter().any(|header| header == "Authorization: OAuth token") {
+                ("401 Unauthorized".to_owned(), vec![])
+            } else if path.starts_with("/me/likes/tracks/1?") {
+                ("200 OK".to_owned(), b"{}".to_vec())
+            } else {
+                ("404 Not Found".to_owned(), vec![])
+            }
+        });
+        let url = |id| Url::parse(&format!("http://{}/me/likes/tracks/{}", addr, id)).unwrap();
+        let mut client = Client::new("abc");
+
+        assert!(client.like_exists(url(1)).is_err());
+
+        client.set_access_token(Some("token"));
+
+        assert!(client.like_exists(url(1)).unwrap());
+        assert!(!client.like_exists(url(2)).unwrap());
+    }
+
+    #[test]
+    fn test_open_stream_rejects_error_status() {
+        use test_support::serve;
+        use track::tests::fixture;
+
+        let addr = serve(|_, _| ("404 Not Found".to_owned(), b"not audio".to_vec()));
+        let mut track = fixture();
+        track.stream_url = Some(format!("http://{}/tracks/262681089/stream", addr));
+
+        let client = Client::new("abc");
+
+        assert!(client.open_stream(&track).is_err());
+        assert!(client.stream(&track, vec![]).is_err());
+    }
+
+    #[test]
+    fn test_stream_transcoding_rejects_error_status() {
+        use serde_json;
+        use media::Preset;
+        use test_support::{host, serve};
+        use track::tests::fixture;
+
+        let addr = serve(|path, headers| {
+            if path.starts_with("/media?") {
+                let body = format!(r#"{{"url": "http://{}/audio"}}"#, host(headers));
+
+                ("200 OK".to_owned(), body.into_bytes())
+            } else {
+                ("404 Not Found".to_owned(), b"not audio".to_vec())
+            }
+        });
+        let mut track = fixture();
+        track.media = Some(serde_json::from_str(&format!(r#"{{
+            "transcodings": [{{
+                "url": "http://{}/media",
+                "preset": "mp3_0_0",
+                "duration": 218932,
+                "snipped": false,
+                "format": {{ "protocol": "progressive", "mime_type": "audio/mpeg" }},
+                "quality": "sq"
+            }}]
+        }}"#, addr)).unwrap());
+
+        let client = Client::new("abc");
+
+        assert!(client.stream_transcoding(&track, &[Preset::ProgressiveMp3], vec![]).is_err());
+    }
+
+    #[test]
+    fn test_download_with_unknown_size() {
+        use progress::Progress;
+        use super::original_size;
+        use test_support::serve;
+        use track::tests::fixture;
+
+        let addr = serve(|path, _| {
+            if path.starts_with("/download?") {
+                ("200 OK".to_owned(), b"original".to_vec())
+            } else {
+                ("404 Not Found".to_owned(), vec![])
+            }
+        });
+        let mut track = fixture();
+        track.download_url = Some(format!("http://{}/download", addr));
+        track.original_content_size = 0;
+
+        assert_eq!(original_size(&track), None);
+
+        let client = Client::new("abc");
+        let mut last_progress: Option<Progress> = None;
+        let len = client.download_with_progress(&track, vec![], |progress| {
+            last_progress = Some(*progress);
+        }).unwrap();
+
+        let last_progress = last_progress.unwrap();
+
+        assert_eq!(len, 8);
+        assert_eq!(last_progress.bytes, 8);
+        assert_eq!(last_progress.total, Some(8));
+        assert_eq!(last_progress.fraction(), Some(1.0));
+
+        track.download_url = Some(format!("http://{}/missing", addr));
+
+        assert!(client.download_with_progress(&track, vec![], |_| {}).is_err());
+    }
+
+    #[test]
+    fn test_download_to_path_does_not_retry_client_errors() {
+        use std::env;
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use super::partial_path;
+        use test_support::serve;
+        use track::tests::fixture;
+
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        let addr = serve(move |_, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            ("404 Not Found".to_owned(), vec![])
+        });
+        let mut track = fixture();
+        track.download_url = Some(format!("http://{}/download", addr));
+
+        let path = env::temp_dir().join("soundcloud-client-missing.wav");
+        let client = Client::new("abc");
+
+        assert!(client.download_to_path(&track, &path).is_err());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        assert!(!path.exists());
+        assert!(!partial_path(&path).exists());
+    }
+
     #[test]
     fn test_resolve_track() {
         let result = client().resolve("https://soundcloud.com/isqa/tree-eater-1");
@@ -312,6 +1284,63 @@ mod tests {
         fs::remove_file(path).unwrap();
     }
 
+    #[test]
+    fn test_download_track_to_path() {
+        use std::fs;
+        use std::path::Path;
+
+        let client = client();
+        let path = Path::new("resumed.mp3");
+        let track = client.tracks().id(263801976).get().unwrap();
+        let size = client.download_to_path(&track, path).unwrap();
+
+        assert_eq!(size as u64, track.original_content_size);
+        assert!(!Path::new("resumed.mp3.part").exists());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_stream_track_with_progress() {
+        let client = client();
+        let track = client.tracks().id(262681089).get().unwrap();
+        let mut last_progress = None;
+        let len = client.stream_with_progress(&track, vec![], |progress| {
+            last_progress = Some(*progress);
+        }).unwrap();
+
+        assert_eq!(last_progress.unwrap().bytes, len as u64);
+    }
+
+    #[test]
+    fn test_open_stream() {
+        use std::io::Read;
+
+        let client = client();
+        let track = client.tracks().id(262681089).get().unwrap();
+        let mut buffer = [0; 1024];
+        let len = client.open_stream(&track).unwrap().read(&mut buffer);
+
+        assert!(len.unwrap() > 0);
+    }
+
+    #[test]
+    fn test_seekable_stream() {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let client = client();
+        let track = client.tracks().id(262681089).get().unwrap();
+        let mut expected = [0; 1040];
+        let mut buffer = [0; 16];
+
+        client.open_stream(&track).unwrap().read_exact(&mut expected).unwrap();
+
+        let mut stream = client.open_seekable_stream(&track).unwrap();
+
+        assert_eq!(stream.seek(SeekFrom::Start(1024)).unwrap(), 1024);
+        stream.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer[..], &expected[1024..]);
+    }
+
     #[test]
     fn test_stream_track() {
         use std::io::BufWriter;
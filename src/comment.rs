@@ -0,0 +1,119 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cmp;
+
+use serde_json;
+
+use client::{Client, User};
+use error::Result;
+
+/// User comment.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Comment {
+    /// Integer ID.
+    pub id: usize,
+    /// API resource URL.
+    pub uri: String,
+    /// Time of creation, as an unparsed string.
+    pub created_at: String,
+    /// HTML comment body.
+    pub body: String,
+    /// Associated timestamp in milliseconds.
+    pub timestamp: Option<usize>,
+    /// User ID of the commenter.
+    pub user_id: usize,
+    /// Small representation of the commenters user.
+    pub user: User,
+    /// The track ID of the related track.
+    pub track_id: usize,
+}
+
+#[derive(Debug)]
+pub struct SingleCommentRequestBuilder<'a> {
+    client: &'a Client,
+    pub id: usize,
+}
+
+impl<'a> SingleCommentRequestBuilder<'a> {
+    /// Constructs a new comment request.
+    pub fn new(client: &'a Client, id: usize) -> SingleCommentRequestBuilder {
+        SingleCommentRequestBuilder {
+            client: client,
+            id: id,
+        }
+    }
+
+    /// Sends the request and returns the comment.
+    pub fn get(&mut self) -> Result<Comment> {
+        let no_params: Option<&[(&str, &str)]> = None;
+        let response = try!(self.client.get(&format!("/comments/{}", self.id), no_params));
+        let comment: Comment = try!(serde_json::from_reader(response));
+
+        Ok(comment)
+    }
+}
+
+/// Groups `comments` by their timestamp into `buckets` equally long parts of a track that is
+/// `duration` milliseconds long.
+///
+/// Comments without a timestamp are left out, and comments with a timestamp past the end of the
+/// track are put in the last bucket.
+///
+/// # Examples
+///
+/// ```no_run
+/// use soundcloud::{Client, group_by_timestamp};
+///
+/// let client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+/// let track = client.track(262681089).get().unwrap();
+/// let comments = client.track(262681089).comments().all().unwrap();
+///
+/// for (index, bucket) in group_by_timestamp(&comments, track.duration, 10).iter().enumerate() {
+///     println!("{}0%: {} comments", index, bucket.len());
+/// }
+/// ```
+pub fn group_by_timestamp(comments: &[Comment], duration: u64, buckets: usize)
+    -> Vec<Vec<&Comment>> {
+    let mut result = vec![vec![]; buckets];
+
+    if buckets == 0 {
+        return result;
+    }
+
+    let duration = cmp::max(duration, 1);
+
+    for comment in comments {
+        if let Some(timestamp) = comment.timestamp {
+            let index = (timestamp as u64).saturating_mul(buckets as u64) / duration;
+
+            result[cmp::min(index as usize, buckets - 1)].push(comment);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use super::*;
+
+    #[test]
+    fn test_group_by_timestamp() {
+        let comments: Vec<Comment> =
+            serde_json::from_str(include_str!("../tests/fixtures/comments.json")).unwrap();
+        let buckets = group_by_timestamp(&comments, 200_000, 4);
+        let ids: Vec<Vec<usize>> = buckets.iter()
+            .map(|bucket| bucket.iter().map(|comment| comment.id).collect())
+            .collect();
+
+        assert_eq!(ids, vec![vec![1, 2], vec![], vec![3], vec![4, 5]]);
+    }
+}
@@ -22,10 +22,21 @@ pub enum Error {
     ApiError(String),
     JsonError(serde_json::Error),
     HttpError(hyper::Error),
+    ImageNotFound(String),
+    InvalidCursor(String),
     InvalidFilter(String),
+    InvalidImageSize(String),
+    InvalidPlaylist(String),
+    InvalidRedirect(String),
+    InvalidTemplate(String),
     Io(io::Error),
+    NotAuthenticated,
+    RedirectLoop(String),
+    SizeMismatch(u64, u64),
+    TooManyRedirects(usize),
     TrackNotDownloadable,
     TrackNotStreamable,
+    UnsupportedEncryption(String),
 }
 
 impl fmt::Display for Error {
@@ -35,9 +46,23 @@ impl fmt::Display for Error {
             Error::HttpError(ref error) => write!(f, "HTTP error: {}", error),
             Error::ApiError(ref error) => write!(f, "SoundCloud error: {}", error),
             Error::Io(ref error) => write!(f, "IO error: {}", error),
+            Error::NotAuthenticated => write!(f, "The client is not authenticated"),
+            Error::ImageNotFound(ref url) => write!(f, "Image not found: {}", url),
+            Error::InvalidCursor(ref cursor) => write!(f, "Invalid cursor: {}", cursor),
             Error::InvalidFilter(_) => write!(f, "Invalid filter"),
+            Error::InvalidImageSize(ref size) => write!(f, "Invalid image size: {}", size),
+            Error::InvalidPlaylist(ref error) => write!(f, "Invalid playlist: {}", error),
+            Error::InvalidRedirect(ref location) =>
+                write!(f, "Invalid redirect location: {}", location),
+            Error::InvalidTemplate(ref error) => write!(f, "Invalid template: {}", error),
+            Error::RedirectLoop(ref url) => write!(f, "Redirect loop detected at {}", url),
+            Error::TooManyRedirects(max) => write!(f, "Too many redirects (max {})", max),
+            Error::SizeMismatch(expected, actual) =>
+                write!(f, "Size mismatch: expected {} bytes, got {}", expected, actual),
             Error::TrackNotStreamable => write!(f, "The track is not available for streaming"),
             Error::TrackNotDownloadable => write!(f, "The track is not available for download"),
+            Error::UnsupportedEncryption(ref method) =>
+                write!(f, "Unsupported encryption method: {}", method),
         }
     }
 }
@@ -47,10 +72,21 @@ impl error::Error for Error {
         match *self {
             Error::InvalidFilter(_) => "invalid filter",
             Error::ApiError(_) => "api error",
+            Error::ImageNotFound(_) => "image not found",
+            Error::InvalidCursor(_) => "invalid cursor",
+            Error::InvalidImageSize(_) => "invalid image size",
+            Error::InvalidPlaylist(_) => "invalid playlist",
+            Error::InvalidRedirect(_) => "invalid redirect",
+            Error::InvalidTemplate(_) => "invalid template",
+            Error::NotAuthenticated => "not authenticated",
+            Error::RedirectLoop(_) => "redirect loop",
+            Error::SizeMismatch(..) => "size mismatch",
+            Error::TooManyRedirects(_) => "too many redirects",
             Error::HttpError(ref error) => error.description(),
             Error::JsonError(ref error) => error.description(),
             Error::TrackNotStreamable => "track is not streamable",
             Error::TrackNotDownloadable => "track is not downloadable",
+            Error::UnsupportedEncryption(_) => "unsupported encryption",
             Error::Io(ref error) => error.description(),
         }
     }
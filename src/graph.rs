@@ -0,0 +1,303 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Traversal of the social graph of followers and followings.
+
+use std::collections::{HashSet, VecDeque};
+
+use client::{Client, User};
+use error::Result;
+use pagination::{Page, Pages};
+use user::SingleUserRequestBuilder;
+
+/// The default number of hops away from the seed user that is walked.
+pub const DEFAULT_DEPTH: usize = 1;
+
+/// The default maximum number of requests sent during a walk.
+pub const DEFAULT_REQUEST_BUDGET: usize = 100;
+
+/// Which relations are followed when walking the graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Users following a user.
+    Followers,
+    /// Users followed by a user.
+    Followings,
+    /// Both followers and followings.
+    Both,
+}
+
+impl Direction {
+    fn includes(&self, relation: Direction) -> bool {
+        *self == Direction::Both || *self == relation
+    }
+}
+
+/// User found during a walk.
+#[derive(Debug, Clone)]
+pub struct Node {
+    /// The user.
+    pub user: User,
+    /// Number of hops from the seed user.
+    pub depth: usize,
+}
+
+/// Subgraph of users found by walking outwards from a seed user.
+#[derive(Debug, Clone)]
+pub struct Graph {
+    /// Users in the order they were found, starting with the seed user.
+    pub nodes: Vec<Node>,
+    /// Follow relations as `(follower id, followed id)` pairs.
+    pub edges: Vec<(usize, usize)>,
+    /// Number of requests sent.
+    pub requests: usize,
+    /// Whether the walk was stopped because the request budget was used up.
+    pub exhausted_budget: bool,
+}
+
+impl Graph {
+    /// Returns the node of the user with the given `id`, if it was found.
+    pub fn node(&self, id: usize) -> Option<&Node> {
+        self.nodes.iter().find(|node| node.user.id == id)
+    }
+
+    /// Returns the users found exactly `depth` hops from the seed user.
+    pub fn at_depth<'a>(&'a self, depth: usize) -> Box<Iterator<Item=&'a User> + 'a> {
+        Box::new(self.nodes.iter().filter(move |node| node.depth == depth).map(|node| &node.user))
+    }
+}
+
+/// Breadth-first walker of the follower graph.
+///
+/// Every page of followers or followings counts as a single request against the budget, and
+/// each user is only visited once no matter how many paths lead to it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use soundcloud::Client;
+/// use soundcloud::graph::{Direction, GraphWalker};
+///
+/// let client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+/// let seed = client.user(1234).get().unwrap();
+/// let graph = GraphWalker::new(&client).direction(Direction::Followings).depth(2).walk(&seed);
+///
+/// for user in graph.unwrap().at_depth(2) {
+///     println!("{}", user.username);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct GraphWalker<'a> {
+    client: &'a Client,
+    direction: Direction,
+    depth: usize,
+    budget: usize,
+}
+
+impl<'a> GraphWalker<'a> {
+    /// Constructs a new walker that follows both directions to the default depth and budget.
+    pub fn new(client: &'a Client) -> GraphWalker<'a> {
+        GraphWalker {
+            client: client,
+            direction: Direction::Both,
+            depth: DEFAULT_DEPTH,
+            budget: DEFAULT_REQUEST_BUDGET,
+        }
+    }
+
+    /// Sets which relations are followed.
+    pub fn direction(&mut self, direction: Direction) -> &mut GraphWalker<'a> {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets the maximum number of hops from the seed user.
+    pub fn depth(&mut self, depth: usize) -> &mut GraphWalker<'a> {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the maximum number of requests sent.
+    pub fn budget(&mut self, budget: usize) -> &mut GraphWalker<'a> {
+        self.budget = budget;
+        self
+    }
+
+    /// Walks the graph outwards from the `seed` user.
+    pub fn walk(&self, seed: &User) -> Result<Graph> {
+        let client = self.client;
+
+        walk(seed, self.direction, self.depth, self.budget, |id, relation| {
+            let user = SingleUserRequestBuilder::new(client, id);
+
+            match relation {
+                Direction::Followers => user.followers(),
+                _ => user.followings(),
+            }
+        })
+    }
+}
+
+/// Pages of users related to a user.
+trait Neighbours: Iterator<Item=Result<Page<User>>> {
+    /// Returns true if there's another page to request.
+    fn has_next(&self) -> bool;
+}
+
+impl<'a> Neighbours for Pages<'a, User> {
+    fn has_next(&self) -> bool {
+        Pages::has_next(self)
+    }
+}
+
+/// Walks the graph breadth-first from `seed`, where `neighbours` returns the pages of users
+/// related to a user id. Each page taken from the iterator counts as one request.
+fn walk<F, I>(seed: &User, direction: Direction, depth: usize, budget: usize, mut neighbours: F)
+    -> Result<Graph> where F: FnMut(usize, Direction) -> I, I: Neighbours {
+    let mut graph = Graph {
+        nodes: vec![Node { user: seed.clone(), depth: 0 }],
+        edges: vec![],
+        requests: 0,
+        exhausted_budget: false,
+    };
+    let mut seen = HashSet::new();
+    let mut seen_edges = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    seen.insert(seed.id);
+    queue.push_back((seed.id, 0));
+
+    'walk: while let Some((id, level)) = queue.pop_front() {
+        if level >= depth {
+            continue;
+        }
+
+        for &relation in &[Direction::Followers, Direction::Followings] {
+            if !direction.includes(relation) {
+                continue;
+            }
+
+            let mut pages = neighbours(id, relation);
+
+            loop {
+                if !pages.has_next() {
+                    break;
+                }
+
+                if graph.requests >= budget {
+                    graph.exhausted_budget = true;
+                    break 'walk;
+                }
+
+                let page = match pages.next() {
+                    Some(page) => try!(page),
+                    None => break,
+                };
+
+                graph.requests += 1;
+
+                for user in page.collection {
+                    let edge = match relation {
+                        Direction::Followers => (user.id, id),
+                        _ => (id, user.id),
+                    };
+
+                    if seen_edges.insert(edge) {
+                        graph.edges.push(edge);
+                    }
+
+                    if seen.insert(user.id) {
+                        queue.push_back((user.id, level + 1));
+                        graph.nodes.push(Node { user: user, depth: level + 1 });
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec;
+
+    use serde_json;
+    use super::*;
+    use super::{Neighbours, walk};
+    use client::User;
+    use error::Result;
+    use pagination::Page;
+
+    impl Neighbours for vec::IntoIter<Result<Page<User>>> {
+        fn has_next(&self) -> bool {
+            self.len() > 0
+        }
+    }
+
+    fn user(id: usize) -> User {
+        serde_json::from_str(&format!(r#"{{
+            "id": {0},
+            "permalink": "user-{0}",
+            "username": "user {0}",
+            "uri": "https://api.soundcloud.com/users/{0}",
+            "permalink_url": "https://soundcloud.com/user-{0}",
+            "avatar_url": "https://a1.sndcdn.com/images/default_avatar_large.png"
+        }}"#, id)).unwrap()
+    }
+
+    /// Returns the followings of `id` in a small graph, one user per page.
+    fn followings(id: usize) -> vec::IntoIter<Result<Page<User>>> {
+        let ids = match id {
+            1 => vec![2, 3],
+            2 => vec![1, 3, 4],
+            3 => vec![4],
+            4 => vec![5],
+            _ => vec![],
+        };
+
+        let pages: Vec<_> = ids.into_iter()
+            .map(|id| Ok(Page { collection: vec![user(id)], next_href: None, future_href: None }))
+            .collect();
+
+        pages.into_iter()
+    }
+
+    #[test]
+    fn test_walk_deduplicates_users() {
+        let graph = walk(&user(1), Direction::Followings, 2, 100, |id, _| followings(id)).unwrap();
+        let nodes: Vec<(usize, usize)> = graph.nodes.iter()
+            .map(|node| (node.user.id, node.depth))
+            .collect();
+
+        assert_eq!(nodes, vec![(1, 0), (2, 1), (3, 1), (4, 2)]);
+        assert_eq!(graph.edges, vec![(1, 2), (1, 3), (2, 1), (2, 3), (2, 4), (3, 4)]);
+        assert_eq!(graph.requests, 6);
+        assert!(!graph.exhausted_budget);
+    }
+
+    #[test]
+    fn test_walk_within_exact_budget() {
+        let graph = walk(&user(1), Direction::Followings, 2, 6, |id, _| followings(id)).unwrap();
+
+        assert_eq!(graph.requests, 6);
+        assert!(!graph.exhausted_budget);
+        assert_eq!(graph.nodes.len(), 4);
+    }
+
+    #[test]
+    fn test_walk_respects_budget() {
+        let graph = walk(&user(1), Direction::Followings, 3, 3, |id, _| followings(id)).unwrap();
+
+        assert_eq!(graph.requests, 3);
+        assert!(graph.exhausted_budget);
+        assert_eq!(graph.at_depth(1).map(|user| user.id).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(graph.at_depth(2).count(), 0);
+    }
+}
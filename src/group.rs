@@ -0,0 +1,197 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use serde_json;
+
+use client::{Client, User};
+use error::Result;
+use pagination::Pages;
+use track::Track;
+
+/// Group of users sharing tracks.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Group {
+    /// Integer ID.
+    pub id: usize,
+    /// API resource URL.
+    pub uri: String,
+    /// Time of which the group was created, as an unparsed string.
+    pub created_at: String,
+    /// Permalink of the resource.
+    pub permalink: String,
+    /// URL to the SoundCloud.com page.
+    pub permalink_url: String,
+    /// URL to a JPEG image.
+    pub artwork_url: Option<String>,
+    /// Name.
+    pub name: String,
+    /// Short description.
+    pub short_description: Option<String>,
+    /// HTML description.
+    pub description: Option<String>,
+    /// Small representation of the creators user.
+    pub creator: Option<User>,
+    /// Number of members.
+    pub members_count: Option<usize>,
+    /// Number of contributors.
+    pub contributors_count: Option<usize>,
+    /// Number of contributed tracks.
+    pub track_count: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct GroupRequestBuilder<'a> {
+    client: &'a Client,
+    query: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct SingleGroupRequestBuilder<'a> {
+    client: &'a Client,
+    pub id: usize,
+}
+
+impl<'a> GroupRequestBuilder<'a> {
+    /// Creates a new group request builder, with no set parameters.
+    pub fn new(client: &'a Client) -> GroupRequestBuilder {
+        GroupRequestBuilder {
+            client: client,
+            query: None,
+        }
+    }
+
+    /// Sets the search query filter, which will only return groups with a matching query.
+    pub fn query<S>(&mut self, query: Option<S>) -> &mut GroupRequestBuilder<'a>
+        where S: AsRef<str> {
+        self.query = query.map(|s| s.as_ref().to_owned());
+        self
+    }
+
+    /// Returns an iterator over the pages of matching groups.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use soundcloud::Client;
+    ///
+    /// let client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+    ///
+    /// for group in client.groups().query(Some("drum and bass")).get().all().unwrap() {
+    ///     println!("{}", group.name);
+    /// }
+    /// ```
+    pub fn get(&self) -> Pages<'a, Group> {
+        let mut params = vec![];
+
+        if let Some(ref query) = self.query {
+            params.push(("q", query.clone()));
+        }
+
+        Pages::new(self.client, "/groups", &params)
+    }
+}
+
+impl<'a> SingleGroupRequestBuilder<'a> {
+    /// Constructs a new group request.
+    pub fn new(client: &'a Client, id: usize) -> SingleGroupRequestBuilder {
+        SingleGroupRequestBuilder {
+            client: client,
+            id: id,
+        }
+    }
+
+    /// Sends the request and returns the group.
+    pub fn get(&mut self) -> Result<Group> {
+        let no_params: Option<&[(&str, &str)]> = None;
+        let response = try!(self.client.get(&format!("/groups/{}", self.id), no_params));
+        let group: Group = try!(serde_json::from_reader(response));
+
+        Ok(group)
+    }
+
+    /// Returns an iterator over the pages of members of the group.
+    pub fn members(&self) -> Pages<'a, User> {
+        self.users("members")
+    }
+
+    /// Returns an iterator over the pages of moderators of the group.
+    pub fn moderators(&self) -> Pages<'a, User> {
+        self.users("moderators")
+    }
+
+    /// Returns an iterator over the pages of users that contributed tracks to the group.
+    pub fn contributors(&self) -> Pages<'a, User> {
+        self.users("contributors")
+    }
+
+    /// Returns an iterator over the pages of tracks contributed to the group.
+    pub fn tracks(&self) -> Pages<'a, Track> {
+        Pages::new(self.client, &format!("/groups/{}/tracks", self.id), &[])
+    }
+
+    fn users(&self, relation: &str) -> Pages<'a, User> {
+        Pages::new(self.client, &format!("/groups/{}/{}", self.id, relation), &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::Deserialize;
+    use serde_json::{self, Value};
+    use super::*;
+    use client::Client;
+    use pagination::{Page, Pages};
+
+    const FIXTURE: &'static str = include_str!("../tests/fixtures/group.json");
+
+    fn path<T: Deserialize>(pages: Pages<T>) -> String {
+        pages.next_url().unwrap().path().to_owned()
+    }
+
+    #[test]
+    fn test_parse_group() {
+        let group: Group = serde_json::from_str(FIXTURE).unwrap();
+
+        assert_eq!(group.id, 3107);
+        assert_eq!(group.name, "Drum & Bass");
+        assert_eq!(group.artwork_url, None);
+        assert_eq!(group.creator.unwrap().username, "Isqa");
+        assert_eq!(group.members_count, Some(48213));
+    }
+
+    #[test]
+    fn test_parse_group_page() {
+        let mut object = BTreeMap::new();
+        object.insert("collection".to_owned(),
+                      Value::Array(vec![serde_json::from_str(FIXTURE).unwrap()]));
+        object.insert("next_href".to_owned(),
+                      Value::String("https://api.soundcloud.com/groups?offset=50".to_owned()));
+
+        let page: Page<Group> = Page::from_value(Value::Object(object)).unwrap();
+
+        assert_eq!(page.collection[0].permalink, "drum-bass");
+        assert!(page.next_href.is_some());
+    }
+
+    #[test]
+    fn test_group_collection_paths() {
+        let client = Client::new("abc");
+        let group = SingleGroupRequestBuilder::new(&client, 3107);
+
+        assert_eq!(path(group.members()), "/groups/3107/members");
+        assert_eq!(path(group.moderators()), "/groups/3107/moderators");
+        assert_eq!(path(group.contributors()), "/groups/3107/contributors");
+        assert_eq!(path(group.tracks()), "/groups/3107/tracks");
+        assert!(group.tracks().next_url().unwrap().query().unwrap()
+            .contains("linked_partitioning=1"));
+        assert_eq!(path(GroupRequestBuilder::new(&client).query(Some("dnb")).get()), "/groups");
+    }
+}
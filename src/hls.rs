@@ -0,0 +1,562 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! HTTP Live Streaming support.
+//!
+//! This module contains a parser for M3U8 media playlists and a `SegmentFetcher` that downloads
+//! the segments of a playlist concurrently while writing them out in order.
+
+use std::cmp;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::sync::{Arc, Condvar, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use url::Url;
+use hyper::header::{Headers, Range};
+use hyper::status::StatusCode;
+
+use client::{self, Client};
+use error::{Error, Result};
+
+/// The default number of segments fetched concurrently.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Parsed M3U8 media playlist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaPlaylist {
+    /// Protocol version, from `#EXT-X-VERSION`.
+    pub version: Option<u64>,
+    /// Maximum segment duration in seconds, from `#EXT-X-TARGETDURATION`.
+    pub target_duration: Option<u64>,
+    /// Sequence number of the first segment, from `#EXT-X-MEDIA-SEQUENCE`.
+    pub media_sequence: u64,
+    /// List of media segments, in playback order.
+    pub segments: Vec<Segment>,
+    /// If the playlist is complete, i.e. it has an `#EXT-X-ENDLIST` tag.
+    pub end_list: bool,
+}
+
+/// Media segment of a playlist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    /// Absolute URL of the segment.
+    pub uri: Url,
+    /// Duration in seconds, from `#EXTINF`.
+    pub duration: f64,
+    /// Optional title, from `#EXTINF`.
+    pub title: Option<String>,
+    /// Sub-range of the resource at `uri`, from `#EXT-X-BYTERANGE`.
+    pub byte_range: Option<ByteRange>,
+    /// Key the segment is encrypted with, from `#EXT-X-KEY`.
+    pub key: Option<Key>,
+}
+
+/// Byte range of a segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    /// Length of the range in bytes.
+    pub length: u64,
+    /// Offset of the first byte.
+    pub offset: u64,
+}
+
+/// Encryption key of a segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Key {
+    /// Encryption method, e.g. `AES-128`.
+    pub method: String,
+    /// Absolute URL of the key.
+    pub uri: Option<Url>,
+    /// Initialization vector, as a hexadecimal string.
+    pub iv: Option<String>,
+}
+
+impl MediaPlaylist {
+    /// Parses the M3U8 media playlist in `input`, resolving relative URIs against `base`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate url;
+    /// # extern crate soundcloud;
+    /// use soundcloud::hls::MediaPlaylist;
+    /// # fn main() {
+    /// let base = url::Url::parse("https://cf-hls-media.sndcdn.com/playlist/a.m3u8").unwrap();
+    /// let playlist = MediaPlaylist::parse("#EXTM3U\n#EXTINF:1.5,\n0.mp3\n#EXT-X-ENDLIST",
+    ///                                     &base).unwrap();
+    ///
+    /// assert_eq!(playlist.segments[0].uri.as_str(),
+    ///            "https://cf-hls-media.sndcdn.com/playlist/0.mp3");
+    /// # }
+    /// ```
+    pub fn parse(input: &str, base: &Url) -> Result<MediaPlaylist> {
+        let mut lines = input.lines().map(str::trim).filter(|line| !line.is_empty());
+
+        if lines.next() != Some("#EXTM3U") {
+            return Err(Error::InvalidPlaylist("missing #EXTM3U header".to_owned()));
+        }
+
+        let mut playlist = MediaPlaylist {
+            version: None,
+            target_duration: None,
+            media_sequence: 0,
+            segments: vec![],
+            end_list: false,
+        };
+
+        let mut key = None;
+        let mut info: Option<(f64, Option<String>)> = None;
+        let mut byte_range: Option<(u64, Option<u64>)> = None;
+
+        for line in lines {
+            if !line.starts_with('#') {
+                let uri = try!(resolve(base, line));
+                let (duration, title) = match info.take() {
+                    Some(info) => info,
+                    None => return Err(invalid(format!("segment without #EXTINF: {}", line))),
+                };
+
+                let byte_range = match byte_range.take() {
+                    Some((length, Some(offset))) => {
+                        Some(ByteRange { length: length, offset: offset })
+                    },
+                    // Without an offset the range continues from the previous segment, which has to
+                    // be a range of the same resource.
+                    Some((length, None)) => match playlist.segments.last() {
+                        Some(&Segment { uri: ref previous, byte_range: Some(range), .. })
+                            if *previous == uri => {
+                            Some(ByteRange { length: length, offset: range.offset + range.length })
+                        },
+                        _ => {
+                            return Err(invalid(format!("#EXT-X-BYTERANGE without offset doesn't \
+                                                        follow a range of {}", uri)));
+                        },
+                    },
+                    None => None,
+                };
+
+                playlist.segments.push(Segment {
+                    uri: uri,
+                    duration: duration,
+                    title: title,
+                    byte_range: byte_range,
+                    key: key.clone(),
+                });
+
+                continue;
+            }
+
+            let (tag, value) = match line.find(':') {
+                Some(pos) => (&line[..pos], &line[pos + 1..]),
+                None => (line, ""),
+            };
+
+            match tag {
+                "#EXTINF" => info = Some(try!(parse_extinf(value))),
+                "#EXT-X-VERSION" => playlist.version = Some(try!(parse_number(tag, value))),
+                "#EXT-X-TARGETDURATION" => {
+                    playlist.target_duration = Some(try!(parse_number(tag, value)))
+                },
+                "#EXT-X-MEDIA-SEQUENCE" => playlist.media_sequence = try!(parse_number(tag, value)),
+                "#EXT-X-BYTERANGE" => byte_range = Some(try!(parse_byte_range(value))),
+                "#EXT-X-KEY" => key = try!(parse_key(value, base)),
+                "#EXT-X-ENDLIST" => playlist.end_list = true,
+                "#EXT-X-STREAM-INF" => {
+                    return Err(invalid("expected a media playlist, got a master playlist"));
+                },
+                // Unknown tags and comments are ignored, as required by the spec.
+                _ => {}
+            }
+        }
+
+        Ok(playlist)
+    }
+
+    /// Returns the total duration of all segments in seconds.
+    pub fn duration(&self) -> f64 {
+        self.segments.iter().map(|segment| segment.duration).sum()
+    }
+}
+
+/// Fetches the segments of a media playlist using the HTTP layer of a `Client`.
+///
+/// Up to `concurrency` segments are downloaded at once, but they are always written to the output
+/// in playlist order. Workers never run more than `concurrency` segments ahead of the output, so
+/// a stalled segment doesn't cause the rest of the track to be buffered in memory.
+#[derive(Debug)]
+pub struct SegmentFetcher {
+    client: Client,
+    concurrency: usize,
+}
+
+impl SegmentFetcher {
+    /// Constructs a new segment fetcher that sends requests with `client`.
+    pub fn new(client: &Client) -> SegmentFetcher {
+        SegmentFetcher {
+            client: client.clone(),
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+
+    /// Sets the number of segments to fetch concurrently.
+    pub fn concurrency(&mut self, concurrency: usize) -> &mut SegmentFetcher {
+        self.concurrency = cmp::max(concurrency, 1);
+        self
+    }
+
+    /// Downloads a single segment and returns its contents.
+    pub fn fetch_segment(&self, segment: &Segment) -> Result<Vec<u8>> {
+        fetch_segment(&self.client, segment)
+    }
+
+    /// Downloads all segments of the `playlist` and writes them in order to the `writer`.
+    ///
+    /// Returns the number of bytes written.
+    pub fn fetch<W: Write>(&self, playlist: &MediaPlaylist, mut writer: W) -> Result<usize> {
+        let segments = Arc::new(playlist.segments.clone());
+        let next_index = Arc::new(AtomicUsize::new(0));
+        let window = Arc::new(Window::new(self.concurrency));
+        let _guard = CloseOnDrop(window.clone());
+        let (sender, receiver) = mpsc::sync_channel(self.concurrency);
+
+        for _ in 0..cmp::min(self.concurrency, segments.len()) {
+            let client = self.client.clone();
+            let segments = segments.clone();
+            let next_index = next_index.clone();
+            let window = window.clone();
+            let sender = sender.clone();
+
+            thread::spawn(move || {
+                loop {
+                    let index = next_index.fetch_add(1, Ordering::SeqCst);
+
+                    if index >= segments.len() || !window.wait(index) {
+                        break;
+                    }
+
+                    let result = fetch_segment(&client, &segments[index]);
+
+                    // The receiver hangs up when the download has failed.
+                    if sender.send((index, result)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut written = 0;
+        let mut len = 0;
+
+        for (index, result) in receiver {
+            pending.insert(index, result);
+
+            while let Some(result) = pending.remove(&written) {
+                let data = try!(result);
+
+                try!(writer.write_all(&data));
+
+                len += data.len();
+                written += 1;
+                window.advance(written);
+            }
+        }
+
+        if written < segments.len() {
+            return Err(invalid(format!("segment {} could not be fetched", written)));
+        }
+
+        Ok(len)
+    }
+}
+
+/// Limits how far ahead of the output the workers of a fetch may download.
+#[derive(Debug)]
+struct Window {
+    /// The number of segments written, and whether the fetch has ended.
+    state: Mutex<(usize, bool)>,
+    condvar: Condvar,
+    size: usize,
+}
+
+impl Window {
+    fn new(size: usize) -> Window {
+        Window {
+            state: Mutex::new((0, false)),
+            condvar: Condvar::new(),
+            size: size,
+        }
+    }
+
+    /// Blocks until the segment at `index` is within the window. Returns false if the fetch has
+    /// ended in the meantime.
+    fn wait(&self, index: usize) -> bool {
+        let mut state = self.state.lock().unwrap();
+
+        while !state.1 && index >= state.0 + self.size {
+            state = self.condvar.wait(state).unwrap();
+        }
+
+        !state.1
+    }
+
+    /// Moves the window to start at the segment at `written`.
+    fn advance(&self, written: usize) {
+        self.state.lock().unwrap().0 = written;
+        self.condvar.notify_all();
+    }
+
+    /// Wakes up all waiting workers and makes them stop.
+    fn close(&self) {
+        self.state.lock().unwrap().1 = true;
+        self.condvar.notify_all();
+    }
+}
+
+/// Closes the window when a fetch returns, including on errors.
+struct CloseOnDrop(Arc<Window>);
+
+impl Drop for CloseOnDrop {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+/// Downloads a single segment with `client` and returns its contents.
+fn fetch_segment(client: &Client, segment: &Segment) -> Result<Vec<u8>> {
+    if let Some(ref key) = segment.key {
+        return Err(Error::UnsupportedEncryption(key.method.clone()));
+    }
+
+    let mut headers = Headers::new();
+
+    if let Some(range) = segment.byte_range {
+        headers.set(Range::bytes(range.offset, range.offset + range.length - 1));
+    }
+
+    let mut response = try!(client::get_url(client, segment.uri.clone(), headers));
+
+    // A server that ignores the range would send the whole resource for every segment.
+    if !response.status.is_success() ||
+       (segment.byte_range.is_some() && response.status != StatusCode::PartialContent) {
+        return Err(Error::ApiError(format!("unexpected status {} for segment {}",
+                                           response.status, segment.uri)));
+    }
+
+    let mut data = vec![];
+    try!(response.read_to_end(&mut data));
+
+    Ok(data)
+}
+
+fn invalid<S: Into<String>>(message: S) -> Error {
+    Error::InvalidPlaylist(message.into())
+}
+
+fn resolve(base: &Url, uri: &str) -> Result<Url> {
+    base.join(uri).map_err(|_| invalid(format!("invalid uri: {}", uri)))
+}
+
+fn parse_number(tag: &str, value: &str) -> Result<u64> {
+    value.parse().map_err(|_| invalid(format!("invalid value for {}: {}", tag, value)))
+}
+
+/// Parses the value of an `#EXTINF` tag: `<duration>,[<title>]`.
+fn parse_extinf(value: &str) -> Result<(f64, Option<String>)> {
+    let (duration, title) = match value.find(',') {
+        Some(pos) => (&value[..pos], &value[pos + 1..]),
+        None => (value, ""),
+    };
+
+    let duration: f64 = try!(duration.trim().parse()
+        .map_err(|_| invalid(format!("invalid segment duration: {}", duration))));
+    let title = if title.is_empty() { None } else { Some(title.to_owned()) };
+
+    Ok((duration, title))
+}
+
+/// Parses the value of an `#EXT-X-BYTERANGE` tag: `<length>[@<offset>]`.
+fn parse_byte_range(value: &str) -> Result<(u64, Option<u64>)> {
+    let mut parts = value.splitn(2, '@');
+    let length = try!(parse_number("#EXT-X-BYTERANGE", parts.next().unwrap_or("")));
+    let offset = match parts.next() {
+        Some(offset) => Some(try!(parse_number("#EXT-X-BYTERANGE", offset))),
+        None => None,
+    };
+
+    if length == 0 {
+        return Err(invalid("empty byte range"));
+    }
+
+    Ok((length, offset))
+}
+
+/// Parses the value of an `#EXT-X-KEY` tag, returning `None` if the method is `NONE`.
+fn parse_key(value: &str, base: &Url) -> Result<Option<Key>> {
+    let mut method = None;
+    let mut uri = None;
+    let mut iv = None;
+
+    for (name, value) in parse_attributes(value) {
+        match name {
+            "METHOD" => method = Some(value.to_owned()),
+            "URI" => uri = Some(try!(resolve(base, value))),
+            "IV" => iv = Some(value.to_owned()),
+            _ => {}
+        }
+    }
+
+    match method {
+        Some(ref method) if method == "NONE" => Ok(None),
+        Some(method) => Ok(Some(Key { method: method, uri: uri, iv: iv })),
+        None => Err(invalid("#EXT-X-KEY without METHOD")),
+    }
+}
+
+/// Splits an attribute list into name and value pairs, removing quotes from quoted values.
+fn parse_attributes(input: &str) -> Vec<(&str, &str)> {
+    let mut result = vec![];
+    let mut rest = input;
+
+    while let Some(eq) = rest.find('=') {
+        let name = rest[..eq].trim();
+        let value_start = &rest[eq + 1..];
+
+        let (value, remainder) = if value_start.starts_with('"') {
+            match value_start[1..].find('"') {
+                Some(end) => (&value_start[1..end + 1], &value_start[end + 2..]),
+                None => (&value_start[1..], ""),
+            }
+        } else {
+            match value_start.find(',') {
+                Some(end) => (&value_start[..end], &value_start[end..]),
+                None => (value_start, ""),
+            }
+        };
+
+        result.push((name, value));
+        rest = remainder.trim_left_matches(',');
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+    use client::Client;
+    use super::*;
+    use super::parse_attributes;
+    use test_support::serve;
+
+    fn base() -> Url {
+        Url::parse("https://cf-hls-media.sndcdn.com/playlist/abc/playlist.m3u8").unwrap()
+    }
+
+    #[test]
+    fn test_parse_media_playlist() {
+        let input = include_str!("../tests/fixtures/media_playlist.m3u8");
+        let playlist = MediaPlaylist::parse(input, &base()).unwrap();
+
+        assert_eq!(playlist.version, Some(3));
+        assert_eq!(playlist.target_duration, Some(10));
+        assert_eq!(playlist.media_sequence, 0);
+        assert!(playlist.end_list);
+        assert_eq!(playlist.segments.len(), 3);
+        assert_eq!(playlist.segments[0].duration, 1.985272);
+        assert_eq!(playlist.segments[0].uri.as_str(),
+                   "https://cf-hls-media.sndcdn.com/media/0/31762/abc.128.mp3");
+        assert_eq!(playlist.segments[2].uri.as_str(),
+                   "https://cf-hls-media.sndcdn.com/playlist/abc/2.mp3");
+        assert_eq!(playlist.segments[1].title, Some("second".to_owned()));
+        assert!(playlist.segments.iter().all(|segment| segment.key.is_none()));
+        assert!((playlist.duration() - 11.985272).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_byte_ranges_and_keys() {
+        let input = include_str!("../tests/fixtures/encrypted_playlist.m3u8");
+        let playlist = MediaPlaylist::parse(input, &base()).unwrap();
+        let segments = &playlist.segments;
+
+        assert_eq!(segments[0].byte_range, Some(ByteRange { length: 1000, offset: 0 }));
+        assert_eq!(segments[1].byte_range, Some(ByteRange { length: 500, offset: 1000 }));
+        assert_eq!(segments[2].byte_range, Some(ByteRange { length: 200, offset: 4000 }));
+        assert!(segments[0].key.is_none());
+
+        let key = segments[1].key.as_ref().unwrap();
+
+        assert_eq!(key.method, "AES-128");
+        assert_eq!(key.uri.as_ref().unwrap().as_str(),
+                   "https://cf-hls-media.sndcdn.com/playlist/abc/key.bin");
+        assert_eq!(key.iv, Some("0x1234".to_owned()));
+        assert!(!playlist.end_list);
+    }
+
+    #[test]
+    fn test_parse_rejects_master_playlist() {
+        let input = "#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=128000\nlow.m3u8\n";
+
+        assert!(MediaPlaylist::parse(input, &base()).is_err());
+        assert!(MediaPlaylist::parse("0.mp3", &base()).is_err());
+    }
+
+    #[test]
+    fn test_parse_byte_range_without_offset_requires_same_resource() {
+        let input = "#EXTM3U\n#EXTINF:1,\n#EXT-X-BYTERANGE:100@0\na.mp3\n\
+                     #EXTINF:1,\n#EXT-X-BYTERANGE:100\nb.mp3\n";
+
+        assert!(MediaPlaylist::parse(input, &base()).is_err());
+    }
+
+    #[test]
+    fn test_parse_attributes() {
+        assert_eq!(parse_attributes(r#"METHOD=AES-128,URI="a,b.bin",IV=0x1"#),
+                   vec![("METHOD", "AES-128"), ("URI", "a,b.bin"), ("IV", "0x1")]);
+    }
+
+    #[test]
+    fn test_fetch_writes_segments_in_order() {
+        // Serve `/<n>.mp3` as the body `segment <n>;`.
+        let addr = serve(|path, _| {
+            ("200 OK".to_owned(), format!("segment {};", &path[1..path.len() - 4]).into_bytes())
+        });
+
+        let base = Url::parse(&format!("http://{}/playlist.m3u8", addr)).unwrap();
+        let input = "#EXTM3U\n#EXTINF:1,\n0.mp3\n#EXTINF:1,\n1.mp3\n#EXTINF:1,\n2.mp3\n\
+                     #EXTINF:1,\n3.mp3\n#EXTINF:1,\n4.mp3\n#EXT-X-ENDLIST\n";
+        let playlist = MediaPlaylist::parse(input, &base).unwrap();
+        let client = Client::new("test");
+        let mut output = vec![];
+
+        let len = SegmentFetcher::new(&client).concurrency(3).fetch(&playlist, &mut output);
+
+        assert_eq!(String::from_utf8(output).unwrap(),
+                   "segment 0;segment 1;segment 2;segment 3;segment 4;");
+        assert_eq!(len.unwrap(), 50);
+    }
+
+    #[test]
+    fn test_fetch_requires_partial_content_for_byte_ranges() {
+        // A server that ignores the `Range` header.
+        let addr = serve(|_, _| ("200 OK".to_owned(), b"whole resource".to_vec()));
+
+        let base = Url::parse(&format!("http://{}/playlist.m3u8", addr)).unwrap();
+        let input = "#EXTM3U\n#EXTINF:1,\n#EXT-X-BYTERANGE:5@0\nmedia.mp3\n#EXT-X-ENDLIST\n";
+        let playlist = MediaPlaylist::parse(input, &base).unwrap();
+        let client = Client::new("test");
+
+        assert!(SegmentFetcher::new(&client).fetch(&playlist, &mut vec![]).is_err());
+    }
+}
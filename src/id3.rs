@@ -0,0 +1,339 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! ID3v2 tagging of downloaded MP3 files.
+//!
+//! Tags are written as ID3v2.3, which is the version most widely supported by players. This
+//! module is only available with the `id3` feature enabled.
+
+use std::cmp;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use error::Result;
+use track::Track;
+
+/// Size of the padding added after the frames, so other taggers can edit the tag in place.
+const PADDING_SIZE: usize = 1024;
+
+/// Picture type of the front cover in an `APIC` frame.
+const PICTURE_TYPE_FRONT_COVER: u8 = 0x03;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Frame {
+    Text(&'static str, String),
+    Comment(String),
+    Picture(String, Vec<u8>),
+}
+
+/// ID3v2.3 tag.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Tag {
+    frames: Vec<Frame>,
+}
+
+impl Tag {
+    /// Constructs a new, empty tag.
+    pub fn new() -> Tag {
+        Tag::default()
+    }
+
+    /// Constructs a tag with the metadata of the `track`.
+    ///
+    /// The title, artist (the uploaders username), genre, bpm, key, year, label name and ISRC are
+    /// set when present, and the tracks permalink url is added as a comment.
+    pub fn from_track(track: &Track) -> Tag {
+        let mut tag = Tag::new();
+
+        tag.set_text("TIT2", &track.title);
+        tag.set_text("TPE1", &track.user.username);
+
+        if let Some(ref genre) = track.genre {
+            tag.set_text("TCON", genre);
+        }
+
+        if let Some(bpm) = track.bpm {
+            tag.set_text("TBPM", &bpm.to_string());
+        }
+
+        if let Some(ref key) = track.key_signature {
+            tag.set_text("TKEY", key);
+        }
+
+        if let Some(year) = track.release_year {
+            tag.set_text("TYER", &year.to_string());
+        }
+
+        if let Some(ref label_name) = track.label_name {
+            tag.set_text("TPUB", label_name);
+        }
+
+        if let Some(ref isrc) = track.isrc {
+            tag.set_text("TSRC", isrc);
+        }
+
+        tag.set_comment(&track.permalink_url);
+        tag
+    }
+
+    /// Sets the text frame with the given `id`, e.g. `TIT2`, replacing any existing value.
+    ///
+    /// Empty values are ignored.
+    pub fn set_text(&mut self, id: &'static str, value: &str) {
+        self.frames.retain(|frame| match *frame {
+            Frame::Text(frame_id, _) => frame_id != id,
+            _ => true,
+        });
+
+        if !value.is_empty() {
+            self.frames.push(Frame::Text(id, value.to_owned()));
+        }
+    }
+
+    /// Returns the value of the text frame with the given `id`.
+    pub fn text(&self, id: &str) -> Option<&str> {
+        self.frames.iter()
+            .filter_map(|frame| match *frame {
+                Frame::Text(frame_id, ref value) if frame_id == id => Some(value.as_str()),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Sets the comment, replacing any existing comment.
+    pub fn set_comment(&mut self, comment: &str) {
+        self.frames.retain(|frame| match *frame {
+            Frame::Comment(_) => false,
+            _ => true,
+        });
+        self.frames.push(Frame::Comment(comment.to_owned()));
+    }
+
+    /// Sets the front cover artwork, replacing any existing artwork.
+    pub fn set_artwork(&mut self, mime_type: &str, data: Vec<u8>) {
+        self.frames.retain(|frame| match *frame {
+            Frame::Picture(..) => false,
+            _ => true,
+        });
+        self.frames.push(Frame::Picture(mime_type.to_owned(), data));
+    }
+
+    /// Returns the encoded tag, including the header and padding.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut frames = vec![];
+
+        for frame in &self.frames {
+            encode_frame(frame, &mut frames);
+        }
+
+        frames.extend(vec![0; PADDING_SIZE]);
+
+        let mut result = Vec::with_capacity(frames.len() + 10);
+
+        result.extend_from_slice(b"ID3");
+        // Version 2.3.0, no flags.
+        result.extend_from_slice(&[3, 0, 0]);
+        result.extend_from_slice(&synchsafe(frames.len() as u32));
+        result.extend(frames);
+        result
+    }
+
+    /// Writes the encoded tag to the `writer`.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+
+    /// Writes the tag to the beginning of the file at `path`, replacing an existing ID3v2 tag.
+    ///
+    /// The file is rewritten to a temporary file next to it, which then replaces the original.
+    pub fn write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let mut data = vec![];
+
+        try!(try!(File::open(path)).read_to_end(&mut data));
+
+        let audio = &data[existing_tag_len(&data)..];
+        let mut temp_name = path.file_name().map(|s| s.to_os_string()).unwrap_or(OsString::new());
+        temp_name.push(".id3tmp");
+
+        let temp_path = path.with_file_name(temp_name);
+
+        {
+            let mut file = try!(File::create(&temp_path));
+
+            try!(self.write_to(&mut file));
+            try!(file.write_all(audio));
+        }
+
+        try!(fs::rename(&temp_path, path));
+
+        Ok(())
+    }
+}
+
+/// Returns the length of the ID3v2 tag at the start of `data`, or 0 if there is none.
+fn existing_tag_len(data: &[u8]) -> usize {
+    if data.len() < 10 || &data[..3] != b"ID3" {
+        return 0;
+    }
+
+    let size = data[6..10].iter()
+        .fold(0usize, |size, &byte| (size << 7) | (byte & 0x7f) as usize);
+    // Version 2.4 tags can have a 10 byte footer.
+    let footer = if data[5] & 0x10 != 0 { 10 } else { 0 };
+
+    cmp::min(10 + size + footer, data.len())
+}
+
+/// Encodes `value` as a synchsafe integer, with the most significant bit of each byte unset.
+fn synchsafe(value: u32) -> [u8; 4] {
+    [((value >> 21) & 0x7f) as u8,
+     ((value >> 14) & 0x7f) as u8,
+     ((value >> 7) & 0x7f) as u8,
+     (value & 0x7f) as u8]
+}
+
+/// Appends `text` with an encoding byte prefix, using ISO-8859-1 if possible and UTF-16
+/// otherwise.
+fn encode_text(text: &str, out: &mut Vec<u8>) {
+    if text.chars().all(|c| (c as u32) < 0x100) {
+        out.push(0);
+        out.extend(text.chars().map(|c| c as u8));
+    } else {
+        out.push(1);
+        out.extend_from_slice(&[0xff, 0xfe]);
+
+        for unit in text.encode_utf16() {
+            out.extend_from_slice(&[(unit & 0xff) as u8, (unit >> 8) as u8]);
+        }
+    }
+}
+
+fn encode_frame(frame: &Frame, out: &mut Vec<u8>) {
+    let mut body = vec![];
+
+    let id = match *frame {
+        Frame::Text(id, ref value) => {
+            encode_text(value, &mut body);
+            id
+        },
+        Frame::Comment(ref comment) => {
+            // The encoding byte comes first, followed by the language and an empty description
+            // that is terminated in the same encoding as the text.
+            let mut text = vec![];
+            encode_text(comment, &mut text);
+
+            body.push(text[0]);
+            body.extend_from_slice(b"eng");
+
+            if text[0] == 0 {
+                body.push(0);
+            } else {
+                body.extend_from_slice(&[0xff, 0xfe, 0, 0]);
+            }
+
+            body.extend_from_slice(&text[1..]);
+            "COMM"
+        },
+        Frame::Picture(ref mime_type, ref data) => {
+            body.push(0);
+            body.extend_from_slice(mime_type.as_bytes());
+            body.push(0);
+            body.push(PICTURE_TYPE_FRONT_COVER);
+            // Empty description.
+            body.push(0);
+            body.extend_from_slice(data);
+            "APIC"
+        },
+    };
+
+    let size = body.len() as u32;
+
+    out.extend_from_slice(id.as_bytes());
+    out.extend_from_slice(&[(size >> 24) as u8, (size >> 16) as u8, (size >> 8) as u8, size as u8]);
+    // No frame flags.
+    out.extend_from_slice(&[0, 0]);
+    out.extend(body);
+}
+
+#[cfg(test)]
+mod tests {
+    use track::tests::fixture;
+    use super::*;
+    use super::{existing_tag_len, synchsafe};
+
+    #[test]
+    fn test_synchsafe() {
+        assert_eq!(synchsafe(0x7f), [0, 0, 0, 0x7f]);
+        assert_eq!(synchsafe(0x80), [0, 0, 1, 0]);
+        assert_eq!(synchsafe(1034), [0, 0, 8, 10]);
+    }
+
+    #[test]
+    fn test_from_track() {
+        let tag = Tag::from_track(&fixture());
+
+        assert_eq!(tag.text("TIT2"), Some("Tree Eater: Part 1/2"));
+        assert_eq!(tag.text("TPE1"), Some("Isqa"));
+        assert_eq!(tag.text("TBPM"), Some("174"));
+        assert_eq!(tag.text("TYER"), Some("2016"));
+        assert_eq!(tag.text("TPUB"), Some("Vision Recordings"));
+        assert_eq!(tag.text("TSRC"), Some("GBKQU1600042"));
+    }
+
+    #[test]
+    fn test_encode_text_frame() {
+        let mut tag = Tag::new();
+        tag.set_text("TIT2", "Hi");
+
+        let bytes = tag.to_bytes();
+
+        assert_eq!(&bytes[..10], &[b'I', b'D', b'3', 3, 0, 0, 0, 0, 8, 13]);
+        assert_eq!(&bytes[10..23], &[b'T', b'I', b'T', b'2', 0, 0, 0, 3, 0, 0, 0, b'H', b'i']);
+        assert_eq!(existing_tag_len(&bytes), bytes.len());
+    }
+
+    #[test]
+    fn test_encode_unicode_text() {
+        let mut tag = Tag::new();
+        tag.set_text("TPE1", "Øπ");
+
+        let bytes = tag.to_bytes();
+
+        assert_eq!(&bytes[20..27], &[1, 0xff, 0xfe, 0xd8, 0x00, 0xc0, 0x03]);
+    }
+
+    #[test]
+    fn test_write_to_path_replaces_existing_tag() {
+        use std::fs::{self, File};
+        use std::io::{Read, Write};
+
+        let path = "id3_test.mp3";
+        let mut tag = Tag::new();
+        tag.set_text("TIT2", "First");
+
+        {
+            let mut file = File::create(path).unwrap();
+            file.write_all(&tag.to_bytes()).unwrap();
+            file.write_all(b"audio").unwrap();
+        }
+
+        tag.set_text("TIT2", "Second");
+        tag.write_to_path(path).unwrap();
+
+        let mut data = vec![];
+        File::open(path).unwrap().read_to_end(&mut data).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(&data[..existing_tag_len(&data)], &tag.to_bytes()[..]);
+        assert_eq!(&data[existing_tag_len(&data)..], b"audio");
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+use std::str;
+
+use error::{Error, Result};
+
+/// All image sizes, from largest to smallest.
+pub const IMAGE_SIZES: &'static [ImageSize] = &[
+    ImageSize::Original,
+    ImageSize::T500x500,
+    ImageSize::Crop,
+    ImageSize::T300x300,
+    ImageSize::Large,
+    ImageSize::T67x67,
+    ImageSize::Badge,
+    ImageSize::Small,
+    ImageSize::Tiny,
+    ImageSize::Mini,
+];
+
+/// Size variant of an artwork or avatar image.
+///
+/// The API returns image urls for the `Large` size, e.g. `...-large.jpg`, and the other sizes are
+/// available by replacing the suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageSize {
+    /// The originally uploaded image, in its original format and size.
+    Original,
+    /// 500x500 pixels.
+    T500x500,
+    /// 400x400 pixels, cropped.
+    Crop,
+    /// 300x300 pixels.
+    T300x300,
+    /// 100x100 pixels. This is the default size of the API.
+    Large,
+    /// 67x67 pixels.
+    T67x67,
+    /// 47x47 pixels.
+    Badge,
+    /// 32x32 pixels.
+    Small,
+    /// 20x20 pixels for avatars, 18x18 pixels for artwork.
+    Tiny,
+    /// 16x16 pixels.
+    Mini,
+}
+
+impl str::FromStr for ImageSize {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ImageSize> {
+        IMAGE_SIZES.iter()
+            .find(|size| size.to_str() == s)
+            .cloned()
+            .ok_or(Error::InvalidImageSize(s.to_owned()))
+    }
+}
+
+impl fmt::Display for ImageSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+impl ImageSize {
+    /// Returns the suffix used for the size in image urls.
+    pub fn to_str(&self) -> &str {
+        match *self {
+            ImageSize::Original => "original",
+            ImageSize::T500x500 => "t500x500",
+            ImageSize::Crop => "crop",
+            ImageSize::T300x300 => "t300x300",
+            ImageSize::Large => "large",
+            ImageSize::T67x67 => "t67x67",
+            ImageSize::Badge => "badge",
+            ImageSize::Small => "small",
+            ImageSize::Tiny => "tiny",
+            ImageSize::Mini => "mini",
+        }
+    }
+
+    /// Returns the width and height in pixels, or `None` for the original image.
+    pub fn dimensions(&self) -> Option<(u32, u32)> {
+        match *self {
+            ImageSize::Original => None,
+            ImageSize::T500x500 => Some((500, 500)),
+            ImageSize::Crop => Some((400, 400)),
+            ImageSize::T300x300 => Some((300, 300)),
+            ImageSize::Large => Some((100, 100)),
+            ImageSize::T67x67 => Some((67, 67)),
+            ImageSize::Badge => Some((47, 47)),
+            ImageSize::Small => Some((32, 32)),
+            ImageSize::Tiny => Some((20, 20)),
+            ImageSize::Mini => Some((16, 16)),
+        }
+    }
+
+    /// Returns this size followed by all smaller sizes, which is the order sizes are tried in when
+    /// a size is missing.
+    pub fn fallbacks(&self) -> &'static [ImageSize] {
+        let index = IMAGE_SIZES.iter().position(|size| size == self).unwrap();
+
+        &IMAGE_SIZES[index..]
+    }
+}
+
+/// Returns the image `url` rewritten to point at the given `size`.
+///
+/// Urls that don't end with a known size suffix are returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use soundcloud::{image_url, ImageSize};
+///
+/// assert_eq!(image_url("https://i1.sndcdn.com/artworks-000162345678-xyzabc-large.jpg",
+///                      ImageSize::T500x500),
+///            "https://i1.sndcdn.com/artworks-000162345678-xyzabc-t500x500.jpg");
+/// ```
+pub fn image_url(url: &str, size: ImageSize) -> String {
+    let (base, query) = match url.find('?') {
+        Some(pos) => (&url[..pos], &url[pos..]),
+        None => (url, ""),
+    };
+
+    let dash = match base.rfind('-') {
+        Some(pos) if !base[pos..].contains('/') => pos,
+        _ => return url.to_owned(),
+    };
+
+    let (suffix, extension) = match base[dash + 1..].find('.') {
+        Some(pos) => (&base[dash + 1..dash + 1 + pos], &base[dash + 1 + pos..]),
+        None => (&base[dash + 1..], ""),
+    };
+
+    if suffix.parse::<ImageSize>().is_err() {
+        return url.to_owned();
+    }
+
+    format!("{}-{}{}{}", &base[..dash], size.to_str(), extension, query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARTWORK_URL: &'static str = "https://i1.sndcdn.com/artworks-000162345678-xyzabc-large.jpg";
+
+    #[test]
+    fn test_image_url() {
+        assert_eq!(image_url(ARTWORK_URL, ImageSize::Crop),
+                   "https://i1.sndcdn.com/artworks-000162345678-xyzabc-crop.jpg");
+        assert_eq!(image_url("https://i1.sndcdn.com/avatars-000212345678-abcdef-t500x500.jpg?1",
+                             ImageSize::Mini),
+                   "https://i1.sndcdn.com/avatars-000212345678-abcdef-mini.jpg?1");
+    }
+
+    #[test]
+    fn test_image_url_without_size_suffix() {
+        let url = "https://a1.sndcdn.com/images/default_avatar_large.png";
+
+        assert_eq!(image_url(url, ImageSize::T500x500), url);
+    }
+
+    #[test]
+    fn test_fallbacks() {
+        assert_eq!(ImageSize::Large.fallbacks(),
+                   &[ImageSize::Large, ImageSize::T67x67, ImageSize::Badge, ImageSize::Small,
+                     ImageSize::Tiny, ImageSize::Mini]);
+        assert_eq!(ImageSize::Original.fallbacks().len(), IMAGE_SIZES.len());
+    }
+
+    #[test]
+    fn test_parse_image_size() {
+        use error::Error;
+
+        assert_eq!("t500x500".parse::<ImageSize>().unwrap(), ImageSize::T500x500);
+
+        match "huge".parse::<ImageSize>() {
+            Err(Error::InvalidImageSize(size)) => assert_eq!(size, "huge"),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
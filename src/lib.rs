@@ -22,13 +22,54 @@ extern crate serde_json;
 /// The static host address for the API.
 pub const API_HOST: &'static str = "api.soundcloud.com";
 
+pub mod batch;
 pub mod error;
+pub mod graph;
+pub mod hls;
+#[cfg(feature = "id3")]
+pub mod id3;
+pub mod progress;
+pub mod recommend;
+pub mod template;
+mod activity;
+mod app;
+mod chart;
 mod client;
+mod comment;
+mod group;
+mod image;
+mod media;
+mod oembed;
+mod pagination;
+mod playlist;
+mod redirect;
+mod repost;
+mod stream;
+#[cfg(test)]
+mod test_support;
 mod track;
+mod user;
+mod waveform;
+mod widget;
 
 // Re-export commonly used resources.
-pub use track::Track;
-pub use client::{User, Comment, App};
+pub use track::{LikedTracks, Track};
+pub use activity::{Activity, Feed};
+pub use image::{ImageSize, image_url};
+pub use oembed::{OEmbed, OEmbedOptions};
+pub use media::{Media, Transcoding, TranscodingFormat, Protocol, Preset};
+pub use app::App;
+pub use chart::{ChartEntry, ChartKind, ChartTrack, Charts};
+pub use client::{User, Quota, Connection, WebProfile};
+pub use comment::{Comment, group_by_timestamp};
+pub use group::Group;
+pub use pagination::{Page, Pages};
+pub use playlist::Playlist;
+pub use repost::{Repost, Reposted};
 pub use client::Client;
 pub use error::Error;
+pub use progress::Progress;
+pub use stream::{TrackStream, SeekableStream};
+pub use waveform::{Waveform, waveform_json_url};
+pub use widget::Widget;
 
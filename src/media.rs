@@ -0,0 +1,217 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+use std::str;
+
+use error::{Error, Result};
+
+/// The presets tried, in order, when streaming a track without an explicit preference.
+pub const DEFAULT_PRESETS: &'static [Preset] = &[
+    Preset::ProgressiveMp3,
+    Preset::HlsMp3,
+    Preset::HlsOpus,
+];
+
+/// Media information of a track.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Media {
+    /// List of available transcodings.
+    pub transcodings: Vec<Transcoding>,
+}
+
+/// Transcoded version of a track.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Transcoding {
+    /// API resource URL which resolves to the signed media URL.
+    pub url: String,
+    /// Name of the preset, e.g. `mp3_0_0`.
+    pub preset: String,
+    /// Duration in milliseconds.
+    pub duration: u64,
+    /// If the transcoding is only a preview snippet of the track.
+    pub snipped: bool,
+    /// Delivery format.
+    pub format: TranscodingFormat,
+    /// Quality, e.g. `sq`.
+    pub quality: Option<String>,
+}
+
+/// Delivery format of a transcoding.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TranscodingFormat {
+    /// Delivery protocol, either `progressive` or `hls`.
+    pub protocol: String,
+    /// MIME type of the audio.
+    pub mime_type: String,
+}
+
+/// Delivery protocol of a transcoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// The audio is delivered as a single file.
+    Progressive,
+    /// The audio is delivered as a HLS playlist of segments.
+    Hls,
+}
+
+impl str::FromStr for Protocol {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Protocol> {
+        match s {
+            "progressive" => Ok(Protocol::Progressive),
+            "hls" => Ok(Protocol::Hls),
+            _ => Err(Error::ApiError(format!("unknown protocol: {}", s))),
+        }
+    }
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+impl Protocol {
+    pub fn to_str(&self) -> &str {
+        match *self {
+            Protocol::Progressive => "progressive",
+            Protocol::Hls => "hls",
+        }
+    }
+}
+
+/// Combination of protocol and codec used to select a transcoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// MP3 delivered as a single file.
+    ProgressiveMp3,
+    /// MP3 delivered over HLS.
+    HlsMp3,
+    /// Opus delivered over HLS.
+    HlsOpus,
+}
+
+impl Preset {
+    /// Returns the protocol of the preset.
+    pub fn protocol(&self) -> Protocol {
+        match *self {
+            Preset::ProgressiveMp3 => Protocol::Progressive,
+            Preset::HlsMp3 | Preset::HlsOpus => Protocol::Hls,
+        }
+    }
+
+    /// Returns true if the `transcoding` is delivered with this preset.
+    pub fn matches(&self, transcoding: &Transcoding) -> bool {
+        let mime_type = &transcoding.format.mime_type;
+        let codec_matches = match *self {
+            Preset::ProgressiveMp3 | Preset::HlsMp3 => mime_type.starts_with("audio/mpeg"),
+            Preset::HlsOpus => mime_type.contains("opus"),
+        };
+
+        codec_matches && transcoding.protocol().ok() == Some(self.protocol())
+    }
+
+    /// Returns the file extension of audio delivered with the preset.
+    pub fn extension(&self) -> &'static str {
+        match *self {
+            Preset::ProgressiveMp3 | Preset::HlsMp3 => "mp3",
+            Preset::HlsOpus => "opus",
+        }
+    }
+}
+
+impl Transcoding {
+    /// Returns the parsed delivery protocol.
+    pub fn protocol(&self) -> Result<Protocol> {
+        self.format.protocol.parse()
+    }
+}
+
+impl Media {
+    /// Returns the first full-length transcoding that matches one of the `presets`, trying them in
+    /// order.
+    pub fn preferred_transcoding(&self, presets: &[Preset]) -> Option<&Transcoding> {
+        presets.iter()
+            .filter_map(|preset| {
+                self.transcodings.iter().find(|t| !t.snipped && preset.matches(t))
+            })
+            .next()
+    }
+
+    /// Returns the first of the `presets` that a full-length transcoding is available for.
+    pub fn preferred_preset(&self, presets: &[Preset]) -> Option<Preset> {
+        presets.iter()
+            .cloned()
+            .find(|preset| self.transcodings.iter().any(|t| !t.snipped && preset.matches(t)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use super::*;
+    use track::Track;
+
+    fn hls_only() -> Media {
+        let track: Track = serde_json::from_str(include_str!("../tests/fixtures/track_hls.json"))
+            .unwrap();
+
+        track.media.unwrap()
+    }
+
+    fn with_progressive() -> Media {
+        let mut media = hls_only();
+        media.transcodings[0].snipped = false;
+        media
+    }
+
+    #[test]
+    fn test_parse_track_media() {
+        let media = hls_only();
+
+        assert_eq!(media.transcodings.len(), 3);
+        assert_eq!(media.transcodings[1].protocol().unwrap(), Protocol::Hls);
+        assert_eq!(media.transcodings[2].format.mime_type, "audio/ogg; codecs=\"opus\"");
+        assert!(media.transcodings[0].snipped);
+    }
+
+    #[test]
+    fn test_preset_matches() {
+        let media = hls_only();
+
+        assert!(Preset::ProgressiveMp3.matches(&media.transcodings[0]));
+        assert!(!Preset::HlsMp3.matches(&media.transcodings[0]));
+        assert!(Preset::HlsMp3.matches(&media.transcodings[1]));
+        assert!(!Preset::HlsOpus.matches(&media.transcodings[1]));
+        assert!(Preset::HlsOpus.matches(&media.transcodings[2]));
+    }
+
+    #[test]
+    fn test_default_presets_prefer_progressive() {
+        let media = with_progressive();
+
+        assert_eq!(media.preferred_preset(DEFAULT_PRESETS), Some(Preset::ProgressiveMp3));
+        assert_eq!(media.preferred_transcoding(DEFAULT_PRESETS).unwrap().url,
+                   media.transcodings[0].url);
+    }
+
+    #[test]
+    fn test_preferred_transcoding_skips_snippets() {
+        let media = hls_only();
+
+        assert_eq!(media.preferred_preset(DEFAULT_PRESETS), Some(Preset::HlsMp3));
+        assert_eq!(media.preferred_transcoding(DEFAULT_PRESETS).unwrap().url,
+                   media.transcodings[1].url);
+        assert!(media.preferred_transcoding(&[Preset::ProgressiveMp3]).is_none());
+        assert_eq!(media.preferred_preset(&[Preset::HlsOpus, Preset::HlsMp3]),
+                   Some(Preset::HlsOpus));
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use error::{Error, Result};
+
+/// The oEmbed endpoint.
+pub const OEMBED_URL: &'static str = "https://soundcloud.com/oembed";
+
+/// Options for an oEmbed request. Options that are `None` are left to SoundCloud's defaults.
+#[derive(Debug, Clone, Default)]
+pub struct OEmbedOptions {
+    /// Maximum width of the player in pixels.
+    pub maxwidth: Option<u32>,
+    /// Maximum height of the player in pixels.
+    pub maxheight: Option<u32>,
+    /// Whether the player starts playing on load.
+    pub auto_play: Option<bool>,
+    /// Primary color of the player, as a hex string without a leading `#`.
+    pub color: Option<String>,
+    /// Whether comments are shown on the waveform.
+    pub show_comments: Option<bool>,
+}
+
+impl OEmbedOptions {
+    /// Constructs options with nothing set.
+    pub fn new() -> OEmbedOptions {
+        OEmbedOptions::default()
+    }
+
+    /// Sets the maximum width of the player.
+    pub fn maxwidth(&mut self, maxwidth: u32) -> &mut OEmbedOptions {
+        self.maxwidth = Some(maxwidth);
+        self
+    }
+
+    /// Sets the maximum height of the player.
+    pub fn maxheight(&mut self, maxheight: u32) -> &mut OEmbedOptions {
+        self.maxheight = Some(maxheight);
+        self
+    }
+
+    /// Sets whether the player starts playing on load.
+    pub fn auto_play(&mut self, auto_play: bool) -> &mut OEmbedOptions {
+        self.auto_play = Some(auto_play);
+        self
+    }
+
+    /// Sets the primary color of the player. A leading `#` is removed.
+    pub fn color<S: AsRef<str>>(&mut self, color: S) -> &mut OEmbedOptions {
+        self.color = Some(color.as_ref().trim_left_matches('#').to_owned());
+        self
+    }
+
+    /// Sets whether comments are shown on the waveform.
+    pub fn show_comments(&mut self, show_comments: bool) -> &mut OEmbedOptions {
+        self.show_comments = Some(show_comments);
+        self
+    }
+
+    /// Returns the options as query parameters.
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![];
+
+        if let Some(maxwidth) = self.maxwidth {
+            params.push(("maxwidth", maxwidth.to_string()));
+        }
+
+        if let Some(maxheight) = self.maxheight {
+            params.push(("maxheight", maxheight.to_string()));
+        }
+
+        if let Some(auto_play) = self.auto_play {
+            params.push(("auto_play", auto_play.to_string()));
+        }
+
+        if let Some(ref color) = self.color {
+            params.push(("color", color.clone()));
+        }
+
+        if let Some(show_comments) = self.show_comments {
+            params.push(("show_comments", show_comments.to_string()));
+        }
+
+        params
+    }
+}
+
+/// oEmbed response with the HTML snippet of an embedded player.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OEmbed {
+    /// oEmbed version.
+    pub version: String,
+    /// Resource type, which is `rich` for players.
+    pub kind: String,
+    /// Name of the provider.
+    pub provider_name: Option<String>,
+    /// URL of the provider.
+    pub provider_url: Option<String>,
+    /// Title of the resource.
+    pub title: Option<String>,
+    /// Description of the resource.
+    pub description: Option<String>,
+    /// URL to a JPEG thumbnail.
+    pub thumbnail_url: Option<String>,
+    /// HTML snippet of the player iframe.
+    pub html: String,
+    /// Name of the resource author.
+    pub author_name: Option<String>,
+    /// URL of the resource author.
+    pub author_url: Option<String>,
+    /// Width of the player, either in pixels or as a percentage, e.g. `100%`.
+    pub width: Option<String>,
+    /// Height of the player, either in pixels or as a percentage.
+    pub height: Option<String>,
+}
+
+impl OEmbed {
+    /// Parses an oEmbed response `value`.
+    ///
+    /// Sizes and the version are accepted as both numbers and strings, since providers are
+    /// inconsistent about it.
+    pub fn from_value(value: Value) -> Result<OEmbed> {
+        let object = match value {
+            Value::Object(object) => object,
+            _ => return Err(Error::ApiError("expected oEmbed response to be an object".to_owned())),
+        };
+
+        let html = match string(&object, "html") {
+            Some(html) => html,
+            None => return Err(Error::ApiError("oEmbed response is missing html".to_owned())),
+        };
+
+        Ok(OEmbed {
+            version: string(&object, "version").unwrap_or("1.0".to_owned()),
+            kind: string(&object, "type").unwrap_or("rich".to_owned()),
+            provider_name: string(&object, "provider_name"),
+            provider_url: string(&object, "provider_url"),
+            title: string(&object, "title"),
+            description: string(&object, "description"),
+            thumbnail_url: string(&object, "thumbnail_url"),
+            html: html,
+            author_name: string(&object, "author_name"),
+            author_url: string(&object, "author_url"),
+            width: string(&object, "width"),
+            height: string(&object, "height"),
+        })
+    }
+}
+
+/// Returns the value of `key` as a string, converting numbers.
+fn string(object: &BTreeMap<String, Value>, key: &str) -> Option<String> {
+    match object.get(key) {
+        Some(&Value::String(ref value)) => Some(value.clone()),
+        Some(&Value::U64(value)) => Some(value.to_string()),
+        Some(&Value::I64(value)) => Some(value.to_string()),
+        Some(&Value::F64(value)) => Some(format!("{:?}", value)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use super::*;
+
+    #[test]
+    fn test_from_value() {
+        let value = serde_json::from_str(r#"{
+            "version": 1.0,
+            "type": "rich",
+            "provider_name": "SoundCloud",
+            "provider_url": "http://soundcloud.com",
+            "height": 400,
+            "width": "100%",
+            "title": "Tree Eater: Part 1/2 by Isqa",
+            "description": null,
+            "thumbnail_url": "http://i1.sndcdn.com/artworks-000162345678-xyzabc-t500x500.jpg",
+            "html": "<iframe width=\"100%\" height=\"400\"></iframe>",
+            "author_name": "Isqa",
+            "author_url": "https://soundcloud.com/isqa"
+        }"#).unwrap();
+        let oembed = OEmbed::from_value(value).unwrap();
+
+        assert_eq!(oembed.version, "1.0");
+        assert_eq!(oembed.width, Some("100%".to_owned()));
+        assert_eq!(oembed.height, Some("400".to_owned()));
+        assert_eq!(oembed.description, None);
+        assert_eq!(oembed.author_name, Some("Isqa".to_owned()));
+    }
+
+    #[test]
+    fn test_params() {
+        let mut options = OEmbedOptions::new();
+        options.maxheight(166).auto_play(true).color("#ff5500");
+
+        assert_eq!(options.params(),
+                   vec![("maxheight", "166".to_owned()),
+                        ("auto_play", "true".to_owned()),
+                        ("color", "ff5500".to_owned())]);
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Pagination of collection endpoints.
+//!
+//! Collections are requested with `linked_partitioning=1`, which makes the API respond with an
+//! object holding a page of the `collection` and a `next_href` url for the next page.
+
+use std::marker::PhantomData;
+
+use url::Url;
+use serde::Deserialize;
+use serde_json::{self, Value};
+
+use client::{self, Client};
+use error::{Error, Result};
+
+/// The default number of resources requested per page.
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// Single page of a collection.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    /// The resources on this page.
+    pub collection: Vec<T>,
+    /// API url of the next page, if there is one.
+    pub next_href: Option<String>,
+    /// API url for polling resources newer than this page, for feeds that support it.
+    pub future_href: Option<String>,
+}
+
+impl<T: Deserialize> Page<T> {
+    /// Parses a page from a response `value`.
+    ///
+    /// Endpoints that don't support pagination respond with a bare array, which is returned as a
+    /// single page.
+    pub fn from_value(value: Value) -> Result<Page<T>> {
+        let (collection, next_href, future_href) = match value {
+            Value::Array(collection) => (collection, None, None),
+            Value::Object(mut object) => {
+                let collection = match object.remove("collection") {
+                    Some(Value::Array(collection)) => collection,
+                    _ => return Err(Error::ApiError("expected a collection".to_owned())),
+                };
+
+                (collection, href(object.get("next_href")), href(object.get("future_href")))
+            },
+            _ => return Err(Error::ApiError("expected response to be a collection".to_owned())),
+        };
+
+        let mut items = Vec::with_capacity(collection.len());
+
+        for value in collection {
+            items.push(try!(serde_json::from_value(value)));
+        }
+
+        Ok(Page {
+            collection: items,
+            next_href: next_href,
+            future_href: future_href,
+        })
+    }
+}
+
+/// Iterator over the pages of a collection.
+///
+/// Each call to `next` sends a request for the following page, until the last page has been
+/// returned or a request fails.
+#[derive(Debug)]
+pub struct Pages<'a, T> {
+    client: &'a Client,
+    next: Option<Url>,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T: Deserialize> Pages<'a, T> {
+    /// Constructs an iterator over the collection at the API endpoint `path`, requesting
+    /// `DEFAULT_PAGE_SIZE` resources per page.
+    pub fn new(client: &'a Client, path: &str, params: &[(&str, String)]) -> Pages<'a, T> {
+        let mut url = client::api_url(client, path);
+
+        url.query_pairs_mut()
+            .append_pair("linked_partitioning", "1")
+            .append_pair("limit", &DEFAULT_PAGE_SIZE.to_string())
+            .extend_pairs(params);
+
+        Pages::from_url(client, url)
+    }
+
+    /// Constructs an iterator starting at the page at `url`, e.g. a `next_href`.
+    pub fn from_url(client: &'a Client, url: Url) -> Pages<'a, T> {
+        Pages {
+            client: client,
+            next: Some(url),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns true if there's another page to request.
+    ///
+    /// Whether there's a page after the next one isn't known until the next one is received.
+    pub fn has_next(&self) -> bool {
+        self.next.is_some()
+    }
+
+    /// Returns the url of the next page to request, if there is one.
+    pub fn next_url(&self) -> Option<&Url> {
+        self.next.as_ref()
+    }
+
+    /// Fetches all remaining pages and returns the resources in a single list.
+    pub fn all(self) -> Result<Vec<T>> {
+        collect_all(self)
+    }
+}
+
+impl<'a, T: Deserialize> Iterator for Pages<'a, T> {
+    type Item = Result<Page<T>>;
+
+    fn next(&mut self) -> Option<Result<Page<T>>> {
+        let url = match self.next.take() {
+            Some(url) => url,
+            None => return None,
+        };
+
+        let page = client::get_json(self.client, url).and_then(Page::from_value);
+
+        if let Ok(ref page) = page {
+            // An empty page is the last one, even if the API includes a `next_href`.
+            if !page.collection.is_empty() {
+                self.next = page.next_href.as_ref().and_then(|href| Url::parse(href).ok());
+            }
+        }
+
+        Some(page)
+    }
+}
+
+/// Fetches all remaining pages of an iterator over pages, like `Pages::all`, and returns the
+/// resources in a single list.
+pub fn collect_all<T, I>(pages: I) -> Result<Vec<T>> where I: Iterator<Item=Result<Page<T>>> {
+    let mut result = vec![];
+
+    for page in pages {
+        result.extend(try!(page).collection);
+    }
+
+    Ok(result)
+}
+
+fn href(value: Option<&Value>) -> Option<String> {
+    value.and_then(Value::as_string).map(str::to_owned)
+}
@@ -0,0 +1,100 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use serde_json;
+
+use client::{Client, User};
+use error::Result;
+use track::Track;
+
+/// Playlist, or set, of tracks.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Playlist {
+    /// Integer ID.
+    pub id: u64,
+    /// Time of which the playlist was created, as an unparsed string.
+    pub created_at: String,
+    /// User ID of the creator.
+    pub user_id: u64,
+    /// Small representation of the creators user.
+    pub user: User,
+    /// Title.
+    pub title: String,
+    /// Permalink of the resource.
+    pub permalink: String,
+    /// URL to the SoundCloud.com page.
+    pub permalink_url: String,
+    /// API resource URL.
+    pub uri: String,
+    /// Sharing status.
+    pub sharing: String,
+    /// Who can embed this playlist.
+    pub embeddable_by: Option<String>,
+    /// External purchase link.
+    pub purchase_url: Option<String>,
+    /// URL to a JPEG image.
+    pub artwork_url: Option<String>,
+    /// HTML description.
+    pub description: Option<String>,
+    /// Duration in milliseconds.
+    pub duration: u64,
+    /// Genre.
+    pub genre: Option<String>,
+    /// List of tags.
+    pub tag_list: Option<String>,
+    /// Label user name.
+    pub label_name: Option<String>,
+    /// Release number.
+    pub release: Option<String>,
+    /// Year of the release.
+    pub release_year: Option<u64>,
+    /// If the playlist is available for stream via the API.
+    pub streamable: Option<bool>,
+    /// If the playlist is available for download.
+    pub downloadable: Option<bool>,
+    /// EAN identifier.
+    pub ean: Option<String>,
+    /// Playlist type, e.g. `album` or `ep single`.
+    pub playlist_type: Option<String>,
+    /// Number of tracks.
+    pub track_count: u64,
+    /// List of tracks. Not included in compact representations.
+    pub tracks: Option<Vec<Track>>,
+}
+
+#[derive(Debug)]
+pub struct SinglePlaylistRequestBuilder<'a> {
+    client: &'a Client,
+    pub id: u64,
+}
+
+impl<'a> SinglePlaylistRequestBuilder<'a> {
+    /// Constructs a new playlist request.
+    pub fn new(client: &'a Client, id: u64) -> SinglePlaylistRequestBuilder {
+        SinglePlaylistRequestBuilder {
+            client: client,
+            id: id,
+        }
+    }
+
+    /// Sends the request and returns the playlist.
+    pub fn get(&mut self) -> Result<Playlist> {
+        let no_params: Option<&[(&str, &str)]> = None;
+        let response = try!(self.client.get(&format!("/playlists/{}", self.id), no_params));
+        let playlist: Playlist = try!(serde_json::from_reader(response));
+
+        Ok(playlist)
+    }
+}
+
+impl PartialEq for Playlist {
+    fn eq(&self, other: &Playlist) -> bool {
+        other.id == self.id
+    }
+}
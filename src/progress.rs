@@ -0,0 +1,114 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Progress reporting for downloads and streams.
+
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant};
+
+/// Size of the buffer used when copying.
+const BUFFER_SIZE: usize = 8 * 1024;
+
+/// Snapshot of the progress of a transfer.
+#[derive(Debug, Clone, Copy)]
+pub struct Progress {
+    /// Number of bytes transferred so far.
+    pub bytes: u64,
+    /// Total number of bytes, if known.
+    pub total: Option<u64>,
+    /// Time elapsed since the transfer started.
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    /// Returns the average throughput in bytes per second.
+    pub fn throughput(&self) -> f64 {
+        let seconds = self.elapsed.as_secs() as f64 +
+            self.elapsed.subsec_nanos() as f64 / 1_000_000_000.0;
+
+        if seconds > 0.0 {
+            self.bytes as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns how much of the transfer is done as a number between `0.0` and `1.0`, if the total
+    /// size is known.
+    pub fn fraction(&self) -> Option<f64> {
+        match self.total {
+            Some(0) => Some(1.0),
+            Some(total) => Some((self.bytes as f64 / total as f64).min(1.0)),
+            None => None,
+        }
+    }
+}
+
+/// Copies the entire contents of `reader` into `writer`, calling `observer` with the current
+/// progress every time a chunk has been written.
+///
+/// Returns the number of bytes copied.
+pub fn copy<R, W, F>(reader: &mut R, writer: &mut W, total: Option<u64>, mut observer: F)
+    -> io::Result<u64> where R: Read, W: Write, F: FnMut(&Progress) {
+    let mut buffer = [0; BUFFER_SIZE];
+    let mut progress = Progress {
+        bytes: 0,
+        total: total,
+        elapsed: Duration::new(0, 0),
+    };
+    let started_at = Instant::now();
+
+    loop {
+        let len = match reader.read(&mut buffer) {
+            Ok(0) => return Ok(progress.bytes),
+            Ok(len) => len,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        try!(writer.write_all(&buffer[..len]));
+
+        progress.bytes += len as u64;
+        progress.elapsed = started_at.elapsed();
+
+        observer(&progress);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use super::*;
+
+    #[test]
+    fn test_copy_reports_progress() {
+        let data = vec![0u8; 20_000];
+        let mut output = vec![];
+        let mut reports = vec![];
+
+        let len = copy(&mut Cursor::new(&data), &mut output, Some(20_000), |progress| {
+            reports.push(progress.bytes);
+        });
+
+        assert_eq!(len.unwrap(), 20_000);
+        assert_eq!(output.len(), 20_000);
+        assert_eq!(reports.last(), Some(&20_000));
+        assert!(reports.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_fraction() {
+        use std::time::Duration;
+
+        let progress = Progress { bytes: 50, total: Some(200), elapsed: Duration::new(1, 0) };
+
+        assert_eq!(progress.fraction(), Some(0.25));
+        assert_eq!(progress.throughput(), 50.0);
+    }
+}
@@ -0,0 +1,310 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Local ranking of tracks by similarity to a seed track.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use track::Track;
+
+/// The default difference in beats per minute at which tempos are considered unrelated.
+pub const DEFAULT_BPM_TOLERANCE: f64 = 10.0;
+
+/// Weights of the individual similarity scores, which are each between `0.0` and `1.0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Weights {
+    /// Weight of the share of common tags.
+    pub tags: f64,
+    /// Weight of having the same genre.
+    pub genre: f64,
+    /// Weight of the proximity in tempo.
+    pub bpm: f64,
+    /// Weight of the harmonic compatibility of the keys.
+    pub key: f64,
+}
+
+impl Default for Weights {
+    fn default() -> Weights {
+        Weights {
+            tags: 0.4,
+            genre: 0.25,
+            bpm: 0.2,
+            key: 0.15,
+        }
+    }
+}
+
+/// Ranked candidate track.
+#[derive(Debug, Clone)]
+pub struct Recommendation<'a> {
+    /// Weighted similarity to the seed track.
+    pub score: f64,
+    /// The candidate track.
+    pub track: &'a Track,
+}
+
+/// Ranks candidate tracks by their similarity to a seed track.
+///
+/// Tracks are compared by shared tags, genre, tempo and key, where tempos are also compared at
+/// half and double time, and keys are compatible when they are next to each other on the Camelot
+/// wheel. Information missing from either track doesn't add to the score.
+///
+/// # Examples
+///
+/// ```no_run
+/// use soundcloud::Client;
+/// use soundcloud::recommend::Recommender;
+///
+/// let client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+/// let seed = client.track(262681089).get().unwrap();
+/// let candidates = client.track(262681089).related().all().unwrap();
+///
+/// for recommendation in Recommender::new(&seed).rank(&candidates).iter().take(5) {
+///     println!("{:.2} {}", recommendation.score, recommendation.track.title);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Recommender<'a> {
+    seed: &'a Track,
+    tags: HashSet<String>,
+    weights: Weights,
+    bpm_tolerance: f64,
+}
+
+impl<'a> Recommender<'a> {
+    /// Constructs a recommender for tracks similar to `seed`, with the default weights.
+    pub fn new(seed: &'a Track) -> Recommender<'a> {
+        Recommender {
+            seed: seed,
+            tags: track_tags(seed),
+            weights: Weights::default(),
+            bpm_tolerance: DEFAULT_BPM_TOLERANCE,
+        }
+    }
+
+    /// Sets the weights of the individual scores.
+    pub fn weights(&mut self, weights: Weights) -> &mut Recommender<'a> {
+        self.weights = weights;
+        self
+    }
+
+    /// Sets the difference in beats per minute at which tempos are considered unrelated.
+    pub fn bpm_tolerance(&mut self, bpm_tolerance: f64) -> &mut Recommender<'a> {
+        self.bpm_tolerance = bpm_tolerance;
+        self
+    }
+
+    /// Returns the weighted similarity of the `track` to the seed track.
+    pub fn score(&self, track: &Track) -> f64 {
+        self.weights.tags * self.tag_score(track) +
+            self.weights.genre * self.genre_score(track) +
+            self.weights.bpm * self.bpm_score(track) +
+            self.weights.key * self.key_score(track)
+    }
+
+    /// Returns the `candidates` ordered by descending score, leaving out the seed track itself.
+    pub fn rank<'b>(&self, candidates: &'b [Track]) -> Vec<Recommendation<'b>> {
+        let mut result: Vec<Recommendation> = candidates.iter()
+            .filter(|track| track.id != self.seed.id)
+            .map(|track| Recommendation { score: self.score(track), track: track })
+            .collect();
+
+        result.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+        result
+    }
+
+    fn tag_score(&self, track: &Track) -> f64 {
+        let tags = track_tags(track);
+        let union = self.tags.union(&tags).count();
+
+        if union == 0 {
+            return 0.0;
+        }
+
+        self.tags.intersection(&tags).count() as f64 / union as f64
+    }
+
+    fn genre_score(&self, track: &Track) -> f64 {
+        match (self.seed.genre.as_ref(), track.genre.as_ref()) {
+            (Some(a), Some(b)) if !a.trim().is_empty() &&
+                                  a.trim().to_lowercase() == b.trim().to_lowercase() => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    fn bpm_score(&self, track: &Track) -> f64 {
+        let (seed_bpm, bpm) = match (self.seed.bpm, track.bpm) {
+            (Some(a), Some(b)) if a > 0 && b > 0 => (a as f64, b as f64),
+            _ => return 0.0,
+        };
+
+        if self.bpm_tolerance <= 0.0 {
+            return if seed_bpm == bpm { 1.0 } else { 0.0 };
+        }
+
+        let difference = [bpm, bpm * 2.0, bpm / 2.0].iter()
+            .map(|&bpm| (seed_bpm - bpm).abs())
+            .fold(::std::f64::INFINITY, f64::min);
+
+        (1.0 - difference / self.bpm_tolerance).max(0.0)
+    }
+
+    fn key_score(&self, track: &Track) -> f64 {
+        let keys = (self.seed.key_signature.as_ref().and_then(|key| camelot(key)),
+                    track.key_signature.as_ref().and_then(|key| camelot(key)));
+
+        match keys {
+            (Some((a, a_minor)), Some((b, b_minor))) => {
+                let distance = (12 + a - b) % 12;
+                let adjacent = distance == 1 || distance == 11;
+
+                if a == b && a_minor == b_minor {
+                    1.0
+                } else if (a == b) || (adjacent && a_minor == b_minor) {
+                    0.5
+                } else {
+                    0.0
+                }
+            },
+            _ => 0.0,
+        }
+    }
+}
+
+/// Splits a tag list into lowercase tags, where multi-word tags are in double quotes.
+///
+/// # Examples
+///
+/// ```
+/// use soundcloud::recommend::parse_tags;
+///
+/// assert_eq!(parse_tags("\"drum and bass\" Neurofunk"), vec!["drum and bass", "neurofunk"]);
+/// ```
+pub fn parse_tags(tag_list: &str) -> Vec<String> {
+    let mut tags = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for c in tag_list.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.trim().is_empty() {
+                    tags.push(current.trim().to_lowercase());
+                }
+
+                current.clear();
+            },
+            c => current.push(c),
+        }
+    }
+
+    if !current.trim().is_empty() {
+        tags.push(current.trim().to_lowercase());
+    }
+
+    tags
+}
+
+/// Returns the position of a key on the Camelot wheel, as a number between 0 and 11 and whether
+/// it's a minor key, or `None` if `key` isn't recognized.
+///
+/// Keys are written as a note with an optional accidental followed by a mode, e.g. `Am`, `F#`,
+/// `Db major` or `Bb minor`.
+fn camelot(key: &str) -> Option<(u8, bool)> {
+    let key = key.trim();
+    let mut chars = key.chars();
+
+    let mut pitch: u8 = match chars.next() {
+        Some('C') | Some('c') => 0,
+        Some('D') | Some('d') => 2,
+        Some('E') | Some('e') => 4,
+        Some('F') | Some('f') => 5,
+        Some('G') | Some('g') => 7,
+        Some('A') | Some('a') => 9,
+        Some('B') | Some('b') => 11,
+        _ => return None,
+    };
+
+    let mut rest = chars.as_str();
+
+    if rest.starts_with('#') || rest.starts_with('♯') {
+        pitch = (pitch + 1) % 12;
+        rest = &rest[rest.chars().next().unwrap().len_utf8()..];
+    } else if rest.starts_with('b') || rest.starts_with('♭') {
+        pitch = (pitch + 11) % 12;
+        rest = &rest[rest.chars().next().unwrap().len_utf8()..];
+    }
+
+    let minor = match &rest.trim().to_lowercase()[..] {
+        "" | "maj" | "major" => false,
+        "m" | "min" | "minor" => true,
+        _ => return None,
+    };
+
+    // Minor keys share their position with the relative major key, three semitones up, and each
+    // step on the wheel is a fifth.
+    let relative_major = if minor { (pitch + 3) % 12 } else { pitch };
+
+    Some(((relative_major * 7 + 7) % 12, minor))
+}
+
+fn track_tags(track: &Track) -> HashSet<String> {
+    track.tags.as_ref()
+        .map(|tags| parse_tags(tags).into_iter().collect())
+        .unwrap_or(HashSet::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::camelot;
+    use track::Track;
+    use track::tests::fixture;
+
+    fn track(id: u64, tags: &str, genre: &str, bpm: u64, key: &str) -> Track {
+        let mut track = fixture();
+
+        track.id = id;
+        track.tags = Some(tags.to_owned());
+        track.genre = Some(genre.to_owned());
+        track.bpm = Some(bpm);
+        track.key_signature = Some(key.to_owned());
+        track
+    }
+
+    #[test]
+    fn test_camelot() {
+        // C major is 8B and A minor is 8A.
+        assert_eq!(camelot("C"), Some((7, false)));
+        assert_eq!(camelot("Am"), Some((7, true)));
+        assert_eq!(camelot("A minor"), camelot("Am"));
+        assert_eq!(camelot("F#m"), camelot("Gbm"));
+        assert_eq!(camelot("Bb major"), Some((5, false)));
+        assert_eq!(camelot("unknown"), None);
+    }
+
+    #[test]
+    fn test_rank() {
+        let seed = track(1, "\"drum and bass\" neurofunk", "Drum & Bass", 174, "Am");
+        let candidates = vec![
+            track(2, "house", "House", 124, "F#"),
+            track(3, "neurofunk", "drum & bass", 87, "Em"),
+            track(4, "\"drum and bass\" neurofunk", "Drum & Bass", 174, "Am"),
+            seed.clone(),
+        ];
+        let ranking = Recommender::new(&seed).rank(&candidates);
+        let ids: Vec<u64> = ranking.iter().map(|recommendation| recommendation.track.id).collect();
+
+        assert_eq!(ids, vec![4, 3, 2]);
+        assert!((ranking[0].score - 1.0).abs() < 1e-9);
+        assert_eq!(ranking[2].score, 0.0);
+    }
+}
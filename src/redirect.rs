@@ -0,0 +1,228 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! HTTP redirect handling.
+//!
+//! The underlying HTTP client is configured to never follow redirects by itself, so that
+//! `Client::resolve` can capture the first `Location` hop. Everything else that needs redirects
+//! followed goes through `follow`.
+
+use std::collections::HashSet;
+
+use url::Url;
+use hyper;
+use hyper::client::Response;
+use hyper::header::{Headers, Location};
+
+use error::{Error, Result};
+
+/// The default maximum number of redirects to follow for a single request.
+pub const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+/// Headers that are removed from the request when a redirect points to another host.
+const SENSITIVE_HEADERS: &'static [&'static str] = &["Authorization", "Cookie"];
+
+/// Sends a HTTP GET request with the given `headers` to `url` and follows up to `max_hops`
+/// redirects, returning the first response that isn't a redirect.
+///
+/// Relative `Location` headers are resolved against the url of the request that returned them,
+/// and authentication headers are stripped from the request once it's redirected to a different
+/// host.
+pub fn follow(client: &hyper::Client, url: Url, mut headers: Headers, max_hops: usize)
+    -> Result<Response> {
+    let mut url = url;
+    let mut visited = HashSet::new();
+
+    loop {
+        let response = try!(client.get(url.clone()).headers(headers.clone()).send());
+
+        if !response.status.is_redirection() {
+            return Ok(response);
+        }
+
+        let location = match response.headers.get::<Location>().cloned() {
+            Some(location) => try!(resolve_location(&url, &location)),
+            None => return Ok(response),
+        };
+
+        if visited.len() >= max_hops {
+            return Err(Error::TooManyRedirects(max_hops));
+        }
+
+        visited.insert(url.clone());
+
+        if visited.contains(&location) {
+            return Err(Error::RedirectLoop(location.into_string()));
+        }
+
+        if !is_same_origin(&url, &location) {
+            for name in SENSITIVE_HEADERS {
+                headers.remove_raw(name);
+            }
+        }
+
+        debug!("Following redirect from {} to {}", url, location);
+
+        url = location;
+    }
+}
+
+/// Resolves the value of a `Location` header, which may be relative, against the `base` url.
+pub fn resolve_location(base: &Url, location: &str) -> Result<Url> {
+    base.join(location).map_err(|_| Error::InvalidRedirect(location.to_owned()))
+}
+
+/// Returns true if both urls have the same scheme, host and port.
+fn is_same_origin(a: &Url, b: &Url) -> bool {
+    a.scheme() == b.scheme() && a.host_str() == b.host_str() &&
+        a.port_or_known_default() == b.port_or_known_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use hyper;
+    use hyper::client::Response;
+    use hyper::header::{Authorization, Headers};
+    use url::Url;
+
+    use error::Error;
+    use super::*;
+    use super::is_same_origin;
+    use test_support::serve_with_headers;
+
+    fn http_client() -> hyper::Client {
+        let mut client = hyper::Client::new();
+        client.set_redirect_policy(hyper::client::RedirectPolicy::FollowNone);
+        client
+    }
+
+    fn redirect(location: &str) -> (String, Vec<String>, Vec<u8>) {
+        ("302 Found".to_owned(), vec![format!("Location: {}", location)], vec![])
+    }
+
+    fn ok(body: &str) -> (String, Vec<String>, Vec<u8>) {
+        ("200 OK".to_owned(), vec![], body.as_bytes().to_vec())
+    }
+
+    /// Serves a chain of redirects from `/1` to `/2` and so on up to `/{hops}`, which responds
+    /// with `200 OK`.
+    fn chain(hops: usize) -> Url {
+        let addr = serve_with_headers(move |path, _| {
+            let hop: usize = path[1..].parse().unwrap();
+
+            if hop < hops {
+                redirect(&format!("/{}", hop + 1))
+            } else {
+                ok("done")
+            }
+        });
+
+        Url::parse(&format!("http://{}/1", addr)).unwrap()
+    }
+
+    fn body(mut response: Response) -> String {
+        let mut body = String::new();
+        response.read_to_string(&mut body).unwrap();
+        body
+    }
+
+    #[test]
+    fn test_follow_redirects() {
+        let response = follow(&http_client(), chain(4), Headers::new(), 3).unwrap();
+
+        assert_eq!(response.url.path(), "/4");
+        assert_eq!(body(response), "done");
+    }
+
+    #[test]
+    fn test_too_many_redirects() {
+        match follow(&http_client(), chain(5), Headers::new(), 3) {
+            Err(Error::TooManyRedirects(3)) => {},
+            result => panic!("unexpected result: {:?}", result.map(|response| response.url)),
+        }
+    }
+
+    #[test]
+    fn test_redirect_loop() {
+        let addr = serve_with_headers(|path, _| {
+            if path == "/a" { redirect("/b") } else { redirect("/a") }
+        });
+        let url = Url::parse(&format!("http://{}/a", addr)).unwrap();
+
+        match follow(&http_client(), url, Headers::new(), 10) {
+            Err(Error::RedirectLoop(location)) => assert!(location.ends_with("/a")),
+            result => panic!("unexpected result: {:?}", result.map(|response| response.url)),
+        }
+    }
+
+    #[test]
+    fn test_sensitive_headers_across_origins() {
+        // Echoes the sensitive headers of the request.
+        let other = serve_with_headers(|_, headers| {
+            let sensitive: Vec<String> = headers.iter()
+                .filter(|header| header.starts_with("Authorization") ||
+                                 header.starts_with("Cookie"))
+                .cloned()
+                .collect();
+
+            ok(&sensitive.join("\n"))
+        });
+        let origin = serve_with_headers(move |path, headers| {
+            match path {
+                "/same" => redirect("/echo"),
+                "/other" => redirect(&format!("http://{}/echo", other)),
+                _ => {
+                    let count = headers.iter()
+                        .filter(|header| header.starts_with("Authorization") ||
+                                         header.starts_with("Cookie"))
+                        .count();
+
+                    ok(&count.to_string())
+                },
+            }
+        });
+        let mut headers = Headers::new();
+        headers.set(Authorization("OAuth token".to_owned()));
+        headers.set_raw("Cookie", vec![b"session=abc".to_vec()]);
+
+        let url = Url::parse(&format!("http://{}/same", origin)).unwrap();
+        let response = follow(&http_client(), url, headers.clone(), 10).unwrap();
+
+        assert_eq!(body(response), "2");
+
+        let url = Url::parse(&format!("http://{}/other", origin)).unwrap();
+        let response = follow(&http_client(), url, headers, 10).unwrap();
+
+        assert_eq!(body(response), "");
+    }
+
+    #[test]
+    fn test_resolve_relative_location() {
+        let base = Url::parse("https://api.soundcloud.com/tracks/1/stream?client_id=x").unwrap();
+
+        assert_eq!(resolve_location(&base, "/tracks/2/stream").unwrap().as_str(),
+                   "https://api.soundcloud.com/tracks/2/stream");
+        assert_eq!(resolve_location(&base, "https://cf-media.sndcdn.com/a.mp3").unwrap().as_str(),
+                   "https://cf-media.sndcdn.com/a.mp3");
+        assert_eq!(resolve_location(&base, "//ec-media.sndcdn.com/b.mp3").unwrap().as_str(),
+                   "https://ec-media.sndcdn.com/b.mp3");
+    }
+
+    #[test]
+    fn test_is_same_origin() {
+        let a = Url::parse("https://api.soundcloud.com/tracks").unwrap();
+        let b = Url::parse("https://api.soundcloud.com:443/users").unwrap();
+        let c = Url::parse("https://cf-media.sndcdn.com/a.mp3").unwrap();
+
+        assert!(is_same_origin(&a, &b));
+        assert!(!is_same_origin(&a, &c));
+    }
+}
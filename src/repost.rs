@@ -0,0 +1,124 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use serde::{de, Deserialize, Deserializer};
+use serde_json::{self, Value};
+
+use client::User;
+use error::{Error, Result};
+use playlist::Playlist;
+use track::Track;
+
+/// The reposted resource of a `Repost`.
+#[derive(Debug, Clone)]
+pub enum Reposted {
+    /// A reposted track.
+    Track(Track),
+    /// A reposted playlist.
+    Playlist(Playlist),
+}
+
+/// Repost of a track or playlist by a user.
+#[derive(Debug, Clone)]
+pub struct Repost {
+    /// Time of the repost, as an unparsed string.
+    pub created_at: String,
+    /// The reposting user.
+    pub user: User,
+    /// The reposted track or playlist.
+    pub item: Reposted,
+}
+
+impl Repost {
+    /// Parses a repost from a collection entry `value`.
+    pub fn from_value(value: Value) -> Result<Repost> {
+        let mut object = match value {
+            Value::Object(object) => object,
+            _ => return Err(Error::ApiError("expected repost to be an object".to_owned())),
+        };
+
+        let created_at = object.get("created_at")
+            .and_then(Value::as_string)
+            .unwrap_or("")
+            .to_owned();
+
+        let user = match object.remove("user") {
+            Some(user) => try!(serde_json::from_value(user)),
+            None => return Err(Error::ApiError("repost is missing a user".to_owned())),
+        };
+
+        let item = match (object.remove("track"), object.remove("playlist")) {
+            (Some(track), _) => Reposted::Track(try!(serde_json::from_value(track))),
+            (None, Some(playlist)) => Reposted::Playlist(try!(serde_json::from_value(playlist))),
+            (None, None) =>
+                return Err(Error::ApiError("repost is missing a track or playlist".to_owned())),
+        };
+
+        Ok(Repost {
+            created_at: created_at,
+            user: user,
+            item: item,
+        })
+    }
+
+    /// Returns the reposted track, if the repost is of a track.
+    pub fn track(&self) -> Option<&Track> {
+        match self.item {
+            Reposted::Track(ref track) => Some(track),
+            _ => None,
+        }
+    }
+
+    /// Returns the reposted playlist, if the repost is of a playlist.
+    pub fn playlist(&self) -> Option<&Playlist> {
+        match self.item {
+            Reposted::Playlist(ref playlist) => Some(playlist),
+            _ => None,
+        }
+    }
+}
+
+impl Deserialize for Repost {
+    fn deserialize<D>(deserializer: &mut D) -> ::std::result::Result<Repost, D::Error>
+        where D: Deserializer {
+        let value = try!(Value::deserialize(deserializer));
+
+        Repost::from_value(value).map_err(|error| de::Error::custom(error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::Value;
+    use super::*;
+    use track::tests::fixture_value;
+
+    #[test]
+    fn test_track_repost() {
+        let track = fixture_value();
+        let user = track.find("user").unwrap().clone();
+        let mut object = BTreeMap::new();
+
+        object.insert("created_at".to_owned(),
+                      Value::String("2016/05/01 12:00:00 +0000".to_owned()));
+        object.insert("type".to_owned(), Value::String("track-repost".to_owned()));
+        object.insert("user".to_owned(), user);
+        object.insert("track".to_owned(), track);
+
+        let repost = Repost::from_value(Value::Object(object)).unwrap();
+
+        assert_eq!(repost.created_at, "2016/05/01 12:00:00 +0000");
+        assert_eq!(repost.user.username, "Isqa");
+        assert_eq!(repost.track().map(|track| track.id), Some(262681089));
+        assert!(repost.playlist().is_none());
+        assert!(Repost::from_value(Value::Null).is_err());
+    }
+}
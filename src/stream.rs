@@ -0,0 +1,228 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+
+use url::Url;
+use hyper::client::Response;
+use hyper::header::{ContentLength, ContentRange, ContentRangeSpec, Headers, Range, ByteRangeSpec};
+use hyper::status::StatusCode;
+
+use client::{self, Client};
+use error::Error;
+
+/// Size of the read-ahead buffer of a `SeekableStream`.
+const READ_AHEAD_SIZE: usize = 64 * 1024;
+
+/// Reader over the audio of a streaming track.
+///
+/// The audio is read directly from the HTTP response body, so dropping the stream before it has
+/// been read to the end closes the connection.
+#[derive(Debug)]
+pub struct TrackStream {
+    response: Response,
+}
+
+impl TrackStream {
+    /// Constructs a new stream reading from the body of `response`.
+    pub fn new(response: Response) -> TrackStream {
+        TrackStream {
+            response: response,
+        }
+    }
+
+    /// Returns the length of the audio in bytes, if the server sent a `Content-Length`.
+    pub fn content_length(&self) -> Option<u64> {
+        self.response.headers.get::<ContentLength>().map(|&ContentLength(len)| len)
+    }
+}
+
+impl Read for TrackStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.response.read(buf)
+    }
+}
+
+/// Reader over the audio of a streaming track that supports seeking.
+///
+/// The audio is fetched on demand using HTTP `Range` requests. A seek within the read-ahead
+/// buffer is served from memory, any other seek drops the connection and reconnects at the new
+/// position on the next read.
+#[derive(Debug)]
+pub struct SeekableStream<'a> {
+    client: &'a Client,
+    url: Url,
+    position: u64,
+    length: Option<u64>,
+    reader: Option<BufReader<Response>>,
+}
+
+/// Constructs a new seekable stream that reads from `url` using `client`.
+///
+/// No request is sent until the stream is read from or seeked relative to the end. This is only
+/// visible within the crate, streams are opened with `Client::open_seekable_stream`.
+pub fn seekable_stream(client: &Client, url: Url) -> SeekableStream {
+    SeekableStream {
+        client: client,
+        url: url,
+        position: 0,
+        length: None,
+        reader: None,
+    }
+}
+
+impl<'a> SeekableStream<'a> {
+    /// Returns the current position in the stream.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the length of the audio in bytes, if it's known yet.
+    pub fn len(&self) -> Option<u64> {
+        self.length
+    }
+
+    /// Returns true if the audio is known to be empty.
+    pub fn is_empty(&self) -> bool {
+        self.length == Some(0)
+    }
+
+    /// Returns true if the stream is positioned at or after the end of the audio.
+    fn is_at_end(&self) -> bool {
+        self.length.map_or(false, |len| self.position >= len)
+    }
+
+    /// Sends a range request starting at the current position and replaces the reader with the
+    /// response body.
+    fn connect(&mut self) -> io::Result<()> {
+        let mut headers = Headers::new();
+        headers.set(Range::Bytes(vec![ByteRangeSpec::AllFrom(self.position)]));
+
+        let response = try!(client::get_url(self.client, self.url.clone(), headers)
+            .map_err(io_error));
+
+        match response.status {
+            StatusCode::PartialContent => {
+                if let Some(len) = instance_length(&response) {
+                    self.length = Some(len);
+                }
+            },
+            StatusCode::Ok if self.position == 0 => {
+                self.length = response.headers.get::<ContentLength>()
+                    .map(|&ContentLength(len)| len);
+            },
+            StatusCode::RangeNotSatisfiable => {
+                self.length = Some(instance_length(&response).unwrap_or(self.position));
+                self.reader = None;
+
+                return Ok(());
+            },
+            status => {
+                return Err(io::Error::new(io::ErrorKind::Other,
+                                          format!("unexpected status: {}", status)));
+            }
+        }
+
+        self.reader = Some(BufReader::with_capacity(READ_AHEAD_SIZE, response));
+
+        Ok(())
+    }
+}
+
+impl<'a> Read for SeekableStream<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.is_at_end() {
+            return Ok(0);
+        }
+
+        if self.reader.is_none() {
+            try!(self.connect());
+        }
+
+        let len = match self.reader {
+            Some(ref mut reader) => try!(reader.read(buf)),
+            None => return Ok(0),
+        };
+
+        self.position += len as u64;
+
+        Ok(len)
+    }
+}
+
+impl<'a> Seek for SeekableStream<'a> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => offset as i64,
+            SeekFrom::Current(offset) => self.position as i64 + offset,
+            SeekFrom::End(offset) => {
+                if self.length.is_none() {
+                    try!(self.connect());
+                }
+
+                match self.length {
+                    Some(len) => len as i64 + offset,
+                    None => return Err(io::Error::new(io::ErrorKind::Other,
+                                                      "the length of the stream is unknown")),
+                }
+            }
+        };
+
+        if target < 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      "invalid seek to a negative position"));
+        }
+
+        let target = target as u64;
+
+        if target == self.position {
+            return Ok(target);
+        }
+
+        // Skip ahead in the read-ahead buffer if the target is already buffered, otherwise drop
+        // the connection and reconnect on the next read.
+        let buffered = match self.reader {
+            Some(ref mut reader) if target > self.position => {
+                let skip = (target - self.position) as usize;
+
+                if reader.fill_buf().map(|buf| buf.len() >= skip).unwrap_or(false) {
+                    reader.consume(skip);
+                    true
+                } else {
+                    false
+                }
+            },
+            _ => false,
+        };
+
+        if !buffered {
+            self.reader = None;
+        }
+
+        self.position = target;
+
+        Ok(target)
+    }
+}
+
+/// Returns the total length of the resource from the `Content-Range` header of the `response`.
+fn instance_length(response: &Response) -> Option<u64> {
+    match response.headers.get::<ContentRange>() {
+        Some(&ContentRange(ContentRangeSpec::Bytes { instance_length, .. })) => instance_length,
+        _ => None,
+    }
+}
+
+/// Converts a library error into an IO error.
+fn io_error(error: Error) -> io::Error {
+    match error {
+        Error::Io(error) => error,
+        error => io::Error::new(io::ErrorKind::Other, error),
+    }
+}
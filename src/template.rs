@@ -0,0 +1,477 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Filename templates for exported tracks.
+//!
+//! A template is a string with track fields in braces, e.g. `{user.username} - {title}`. Rendered
+//! templates are safe to use as file names on Windows, macOS and Linux: reserved characters are
+//! replaced, reserved device names are escaped and the length is limited.
+
+use std::cmp;
+use std::collections::HashSet;
+use std::str;
+
+use error::{Error, Result};
+use media::{self, Preset};
+use track::Track;
+
+/// The template used when none is given.
+pub const DEFAULT_TEMPLATE: &'static str = "{user.username} - {title}.{ext}";
+
+/// The default maximum length of a rendered file name in bytes.
+pub const DEFAULT_MAX_LEN: usize = 255;
+
+/// Characters that aren't allowed in file names on at least one common platform.
+const RESERVED_CHARS: &'static [char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Device names that can't be used as file names on Windows, regardless of extension.
+const RESERVED_NAMES: &'static [&'static str] = &[
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Track field that can be used in a template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    /// `{id}`
+    Id,
+    /// `{title}`
+    Title,
+    /// `{user.username}`
+    Username,
+    /// `{original_format}`
+    OriginalFormat,
+    /// `{ext}`, the extension of the file written by a batch download: the original format for
+    /// downloadable tracks, otherwise the codec of the stream.
+    Extension,
+    /// `{release_year}`, empty if unknown.
+    ReleaseYear,
+    /// `{genre}`, empty if unknown.
+    Genre,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Field(Field),
+}
+
+/// Parsed filename template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+    max_len: usize,
+}
+
+/// Set of file names that makes colliding names unique by appending a counter, e.g.
+/// `Title (1).mp3`.
+///
+/// Names are compared case-insensitively, since that's how most desktop file systems behave.
+/// The stem is truncated further where needed to keep names with a counter within the maximum
+/// length.
+#[derive(Debug, Clone)]
+pub struct UniqueNames {
+    used: HashSet<String>,
+    max_len: usize,
+}
+
+impl str::FromStr for Field {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Field> {
+        match s {
+            "id" => Ok(Field::Id),
+            "title" => Ok(Field::Title),
+            "user.username" => Ok(Field::Username),
+            "original_format" => Ok(Field::OriginalFormat),
+            "ext" => Ok(Field::Extension),
+            "release_year" => Ok(Field::ReleaseYear),
+            "genre" => Ok(Field::Genre),
+            _ => Err(Error::InvalidTemplate(format!("unknown field: {}", s))),
+        }
+    }
+}
+
+impl Field {
+    /// Returns the value of the field for the `track`.
+    pub fn value(&self, track: &Track) -> String {
+        match *self {
+            Field::Id => track.id.to_string(),
+            Field::Title => track.title.clone(),
+            Field::Username => track.user.username.clone(),
+            Field::OriginalFormat => track.original_format.clone(),
+            Field::Extension => extension(track).to_owned(),
+            Field::ReleaseYear => track.release_year.map(|year| year.to_string())
+                .unwrap_or(String::new()),
+            Field::Genre => track.genre.clone().unwrap_or(String::new()),
+        }
+    }
+}
+
+impl str::FromStr for Template {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Template> {
+        Template::parse(s)
+    }
+}
+
+impl Default for Template {
+    fn default() -> Template {
+        Template::parse(DEFAULT_TEMPLATE).unwrap()
+    }
+}
+
+impl Template {
+    /// Parses a template string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use soundcloud::template::Template;
+    ///
+    /// assert!(Template::parse("{user.username} - {title}.{original_format}").is_ok());
+    /// assert!(Template::parse("{title").is_err());
+    /// ```
+    pub fn parse(input: &str) -> Result<Template> {
+        let mut parts = vec![];
+        let mut rest = input;
+
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                parts.push(Part::Literal(rest[..start].to_owned()));
+            }
+
+            let end = match rest[start..].find('}') {
+                Some(end) => start + end,
+                None => return Err(Error::InvalidTemplate("unclosed {".to_owned())),
+            };
+
+            parts.push(Part::Field(try!(rest[start + 1..end].trim().parse())));
+            rest = &rest[end + 1..];
+        }
+
+        if !rest.is_empty() {
+            parts.push(Part::Literal(rest.to_owned()));
+        }
+
+        Ok(Template {
+            parts: parts,
+            max_len: DEFAULT_MAX_LEN,
+        })
+    }
+
+    /// Sets the maximum length of a rendered file name in bytes.
+    pub fn max_len(&mut self, max_len: usize) -> &mut Template {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Renders the template for the `track` as a sanitized file name.
+    ///
+    /// The result is always a single path component that is no longer than the maximum length.
+    pub fn render(&self, track: &Track) -> String {
+        let name: String = self.parts.iter()
+            .map(|part| match *part {
+                Part::Literal(ref literal) => literal.clone(),
+                Part::Field(field) => field.value(track),
+            })
+            .collect();
+
+        // Truncating can uncover a reserved name or leave a trailing dot, so the result is
+        // sanitized again, which in turn may have to be truncated once more.
+        let name = sanitize(&truncate(&sanitize(&name), self.max_len));
+
+        truncate(&name, self.max_len)
+    }
+
+    /// Returns an empty set of names with the same maximum length as the template.
+    pub fn unique_names(&self) -> UniqueNames {
+        UniqueNames::with_max_len(self.max_len)
+    }
+}
+
+impl Default for UniqueNames {
+    fn default() -> UniqueNames {
+        UniqueNames::with_max_len(DEFAULT_MAX_LEN)
+    }
+}
+
+impl UniqueNames {
+    /// Constructs a new, empty set of names with the default maximum length.
+    pub fn new() -> UniqueNames {
+        UniqueNames::default()
+    }
+
+    /// Constructs a new, empty set of names that are no longer than `max_len` bytes.
+    pub fn with_max_len(max_len: usize) -> UniqueNames {
+        UniqueNames {
+            used: HashSet::new(),
+            max_len: max_len,
+        }
+    }
+
+    /// Adds `name` to the set and returns it, with a counter inserted before the extension if
+    /// the name was already taken.
+    ///
+    /// If the maximum length leaves no room for the stem next to the counter, the name is
+    /// replaced by the counter and the extension, or by the counter alone.
+    pub fn insert(&mut self, name: &str) -> String {
+        let (stem, extension) = split_extension(name);
+        let mut candidate = name.to_owned();
+        let mut counter = 0;
+
+        while !self.used.insert(candidate.to_lowercase()) {
+            counter += 1;
+            candidate = numbered(stem, extension, counter, self.max_len);
+        }
+
+        candidate
+    }
+}
+
+/// Returns the name made of `stem`, `counter` and `extension`, e.g. `Title (1).mp3`, with the
+/// stem truncated to keep the name within `max_len` bytes.
+fn numbered(stem: &str, extension: &str, counter: usize, max_len: usize) -> String {
+    let suffix = format!(" ({}){}", counter, extension);
+
+    if suffix.len() < max_len {
+        let stem_len = cmp::min(max_len - suffix.len(), stem.len());
+        let stem = stem[..floor_char_boundary(stem, stem_len)].trim_right();
+
+        if !stem.is_empty() {
+            return format!("{}{}", stem, suffix);
+        }
+    }
+
+    let counter = format!("({})", counter);
+
+    if counter.len() + extension.len() <= max_len {
+        format!("{}{}", counter, extension)
+    } else {
+        counter
+    }
+}
+
+/// Returns `name` with characters that aren't allowed in file names replaced by `_`.
+///
+/// Control characters are replaced as well, trailing dots and spaces are removed and Windows
+/// device names like `CON` are prefixed with `_`.
+///
+/// # Examples
+///
+/// ```
+/// use soundcloud::template::sanitize;
+///
+/// assert_eq!(sanitize("AC/DC: Live?"), "AC_DC_ Live_");
+/// assert_eq!(sanitize("nul.mp3"), "_nul.mp3");
+/// ```
+pub fn sanitize(name: &str) -> String {
+    let name: String = name.chars()
+        .map(|c| if c.is_control() || RESERVED_CHARS.contains(&c) { '_' } else { c })
+        .collect();
+    let name = name.trim().trim_right_matches(|c: char| c == '.' || c == ' ');
+
+    if name.is_empty() {
+        return "_".to_owned();
+    }
+
+    let stem = name.split('.').next().unwrap_or("").trim_right().to_uppercase();
+
+    if RESERVED_NAMES.contains(&stem.as_str()) {
+        format!("_{}", name)
+    } else {
+        name.to_owned()
+    }
+}
+
+/// Truncates `name` to at most `max_len` bytes without splitting characters, keeping the
+/// extension intact when possible.
+pub fn truncate(name: &str, max_len: usize) -> String {
+    if name.len() <= max_len {
+        return name.to_owned();
+    }
+
+    let (stem, extension) = split_extension(name);
+
+    if extension.len() >= max_len {
+        return name[..floor_char_boundary(name, max_len)].to_owned();
+    }
+
+    let stem = &stem[..floor_char_boundary(stem, max_len - extension.len())];
+
+    format!("{}{}", stem.trim_right(), extension)
+}
+
+/// Returns the extension of the file `Client::download_to_path` or `Client::stream` writes for
+/// the `track`.
+fn extension(track: &Track) -> &str {
+    if track.downloadable && track.download_url.is_some() {
+        return &track.original_format;
+    }
+
+    if track.stream_url.is_some() {
+        return Preset::ProgressiveMp3.extension();
+    }
+
+    track.media.as_ref()
+        .and_then(|media| media.preferred_preset(media::DEFAULT_PRESETS))
+        .unwrap_or(Preset::ProgressiveMp3)
+        .extension()
+}
+
+/// Splits a file name into its stem and its extension, including the dot.
+fn split_extension(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(pos) if pos > 0 => (&name[..pos], &name[pos..]),
+        _ => (name, ""),
+    }
+}
+
+/// Returns the largest index not greater than `index` that is on a character boundary.
+fn floor_char_boundary(s: &str, index: usize) -> usize {
+    let mut index = index;
+
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use media::{Media, Transcoding, TranscodingFormat};
+    use track::tests::fixture;
+    use super::*;
+
+    #[test]
+    fn test_render_default_template() {
+        assert_eq!(Template::default().render(&fixture()), "Isqa - Tree Eater_ Part 1_2.wav");
+    }
+
+    #[test]
+    fn test_render_optional_fields() {
+        let template = Template::parse("{release_year} {genre} - {id}").unwrap();
+
+        assert_eq!(template.render(&fixture()), "2016 Drum & Bass - 262681089");
+    }
+
+    #[test]
+    fn test_render_stream_extension() {
+        let template = Template::parse("{id}.{ext}").unwrap();
+        let mut track = fixture();
+        track.downloadable = false;
+
+        assert_eq!(template.render(&track), "262681089.mp3");
+
+        track.stream_url = None;
+        track.media = Some(Media {
+            transcodings: vec![Transcoding {
+                url: "https://api-v2.soundcloud.com/media/262681089/opus/stream/hls".to_owned(),
+                preset: "opus_0_0".to_owned(),
+                duration: 288000,
+                snipped: false,
+                format: TranscodingFormat {
+                    protocol: "hls".to_owned(),
+                    mime_type: "audio/ogg; codecs=\"opus\"".to_owned(),
+                },
+                quality: Some("sq".to_owned()),
+            }],
+        });
+
+        assert_eq!(template.render(&track), "262681089.opus");
+    }
+
+    #[test]
+    fn test_sanitize() {
+        assert_eq!(sanitize("a<b>c|d*e\u{7}"), "a_b_c_d_e_");
+        assert_eq!(sanitize("trailing dots... "), "trailing dots");
+        assert_eq!(sanitize("Com1.wav"), "_Com1.wav");
+        assert_eq!(sanitize("Console.wav"), "Console.wav");
+        assert_eq!(sanitize(" . "), "_");
+    }
+
+    #[test]
+    fn test_truncate_keeps_extension() {
+        assert_eq!(truncate("abcdefgh.mp3", 8), "abcd.mp3");
+        assert_eq!(truncate("ææææ.mp3", 9), "ææ.mp3");
+        assert_eq!(truncate("short.mp3", 255), "short.mp3");
+
+        let mut template = Template::default();
+        template.max_len(20);
+
+        assert_eq!(template.render(&fixture()).len(), 20);
+    }
+
+    #[test]
+    fn test_unique_names() {
+        let mut names = UniqueNames::new();
+
+        assert_eq!(names.insert("Title.mp3"), "Title.mp3");
+        assert_eq!(names.insert("title.mp3"), "title (1).mp3");
+        assert_eq!(names.insert("Title.mp3"), "Title (2).mp3");
+        assert_eq!(names.insert("Other"), "Other");
+        assert_eq!(names.insert("Other"), "Other (1)");
+    }
+
+    #[test]
+    fn test_render_sanitizes_truncated_name() {
+        let mut template = Template::parse("{title}.wav").unwrap();
+        let mut track = fixture();
+        track.title = "Console".to_owned();
+
+        assert_eq!(template.render(&track), "Console.wav");
+        assert_eq!(template.max_len(7).render(&track), "_Co.wav");
+
+        track.title = "Intro. Outro".to_owned();
+        template = Template::parse("{title}").unwrap();
+
+        assert_eq!(template.max_len(7).render(&track), "Intro");
+    }
+
+    #[test]
+    fn test_unique_names_without_room_for_stem() {
+        let mut names = UniqueNames::with_max_len(9);
+
+        assert_eq!(names.insert("abc.flac"), "abc.flac");
+        assert_eq!(names.insert("abc.flac"), "(1).flac");
+
+        let mut names = UniqueNames::with_max_len(6);
+
+        assert_eq!(names.insert("ab.mp3"), "ab.mp3");
+        assert_eq!(names.insert("ab.mp3"), "(1)");
+        assert_eq!(names.insert("ab.mp3"), "(2)");
+    }
+
+    #[test]
+    fn test_unique_names_stay_within_max_len() {
+        let mut names = UniqueNames::with_max_len(12);
+
+        assert_eq!(names.insert("abcdefgh.mp3"), "abcdefgh.mp3");
+        assert_eq!(names.insert("abcdefgh.mp3"), "abcd (1).mp3");
+        assert_eq!(names.insert("abcdefgh.mp3"), "abcd (2).mp3");
+        assert_eq!(names.insert("ææææ.mp3"), "ææææ.mp3");
+        assert_eq!(names.insert("ææææ.mp3"), "ææ (1).mp3");
+
+        let mut template = Template::default();
+        template.max_len(20);
+        let mut names = template.unique_names();
+        let name = template.render(&fixture());
+
+        assert_eq!(names.insert(&name).len(), 20);
+        assert!(names.insert(&name).len() <= 20);
+    }
+
+    #[test]
+    fn test_parse_unknown_field() {
+        assert!(Template::parse("{title} {artist}").is_err());
+    }
+}
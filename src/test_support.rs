@@ -0,0 +1,77 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Helpers shared by the tests of several modules.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::thread;
+
+/// Serves HTTP requests on a local port until the test process exits, and returns its address.
+///
+/// The `handler` is called with the request path and header lines, and returns the status, e.g.
+/// `200 OK`, and the body of the response.
+pub fn serve<F>(handler: F) -> SocketAddr
+    where F: Fn(&str, &[String]) -> (String, Vec<u8>) + Send + 'static {
+    serve_with_headers(move |path, headers| {
+        let (status, body) = handler(path, headers);
+        (status, vec![], body)
+    })
+}
+
+/// Serves HTTP requests like `serve`, with a `handler` that also returns extra header lines of
+/// the response, e.g. `Location: /next`.
+pub fn serve_with_headers<F>(handler: F) -> SocketAddr
+    where F: Fn(&str, &[String]) -> (String, Vec<String>, Vec<u8>) + Send + 'static {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request_line = String::new();
+            let mut headers = vec![];
+
+            {
+                let mut reader = BufReader::new(&stream);
+                let mut line = String::new();
+
+                reader.read_line(&mut request_line).unwrap();
+
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    headers.push(line.trim().to_owned());
+                    line.clear();
+                }
+            }
+
+            let path = request_line.split(' ').nth(1).unwrap().to_owned();
+            let (status, extra_headers, body) = handler(&path, &headers);
+            let extra_headers: String = extra_headers.iter()
+                .map(|header| format!("{}\r\n", header))
+                .collect();
+
+            // The client may hang up early, e.g. after a failed request.
+            let _ = write!(stream,
+                           "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n",
+                           status, extra_headers, body.len())
+                .and_then(|_| stream.write_all(&body));
+        }
+    });
+
+    addr
+}
+
+/// Returns the value of the `Host` header of a request, e.g. `127.0.0.1:1234`, for building
+/// absolute urls that point back at the server.
+pub fn host(headers: &[String]) -> String {
+    headers.iter()
+        .find(|header| header.starts_with("Host: "))
+        .map(|header| header[6..].to_owned())
+        .unwrap()
+}
@@ -14,7 +14,12 @@ use url::Url;
 use serde_json;
 
 use error::{Error, Result};
-use client::{Client, User, App};
+use app::App;
+use client::{Client, User};
+use comment::Comment;
+use image::{ImageSize, image_url};
+use media::Media;
+use pagination::{self, Page, Pages};
 
 #[derive(Debug)]
 pub enum Filter {
@@ -87,7 +92,8 @@ pub struct Track {
     pub duration: u64,
     /// Genre.
     pub genre: Option<String>,
-    /// List of tags.
+    /// List of tags, separated by spaces and with multi-word tags in quotes.
+    #[serde(rename="tag_list")]
     pub tags: Option<String>,
     /// Label user ID.
     pub label_id: Option<u64>,
@@ -119,6 +125,8 @@ pub struct Track {
     pub download_url: Option<String>,
     /// URL to 128kbps mp3 stream.
     pub stream_url: Option<String>,
+    /// Available transcodings, for tracks that aren't streamable via `stream_url`.
+    pub media: Option<Media>,
     /// External video link.
     pub video_url: Option<String>,
     /// Beats per minute.
@@ -151,6 +159,15 @@ pub struct Track {
     pub user_favorite: Option<bool>,
 }
 
+/// Iterator over the pages of tracks liked by the authenticated user.
+///
+/// The `user_favorite` field of every track is set, since the API leaves it out of the
+/// collection.
+#[derive(Debug)]
+pub struct LikedTracks<'a> {
+    pages: Pages<'a, Track>,
+}
+
 #[derive(Debug)]
 pub struct TrackRequestBuilder<'a> {
     client: &'a Client,
@@ -189,6 +206,27 @@ impl<'a> SingleTrackRequestBuilder<'a> {
         Ok(track)
     }
 
+    /// Returns an iterator over the pages of comments on the track.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use soundcloud::Client;
+    ///
+    /// let client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+    /// let first_page = client.track(262681089).comments().next().unwrap();
+    ///
+    /// assert!(first_page.is_ok());
+    /// ```
+    pub fn comments(&self) -> Pages<'a, Comment> {
+        Pages::new(self.client, &format!("/tracks/{}/comments", self.id), &[])
+    }
+
+    /// Returns an iterator over the pages of tracks related to the track.
+    pub fn related(&self) -> Pages<'a, Track> {
+        Pages::new(self.client, &format!("/tracks/{}/related", self.id), &[])
+    }
+
     pub fn request_url(&self) -> Url {
         let url = Url::parse(&format!("https://{}/tracks/{}", super::API_HOST, self.id)).unwrap();
 
@@ -328,8 +366,85 @@ impl<'a> TrackRequestBuilder<'a> {
     }
 }
 
+impl Track {
+    /// Returns the url of the tracks artwork in the given `size`, if the track has artwork.
+    pub fn artwork_url_for(&self, size: ImageSize) -> Option<String> {
+        self.artwork_url.as_ref().map(|url| image_url(url, size))
+    }
+}
+
+impl<'a> LikedTracks<'a> {
+    /// Constructs an iterator over the tracks liked by the authenticated user.
+    pub fn new(client: &'a Client) -> LikedTracks<'a> {
+        LikedTracks {
+            pages: Pages::new(client, "/me/likes/tracks", &[]),
+        }
+    }
+
+    /// Fetches all remaining pages and returns the tracks in a single list.
+    pub fn all(self) -> Result<Vec<Track>> {
+        pagination::collect_all(self)
+    }
+}
+
+impl<'a> Iterator for LikedTracks<'a> {
+    type Item = Result<Page<Track>>;
+
+    fn next(&mut self) -> Option<Result<Page<Track>>> {
+        self.pages.next().map(|page| {
+            page.map(|mut page| {
+                for track in &mut page.collection {
+                    track.user_favorite = Some(true);
+                }
+
+                page
+            })
+        })
+    }
+}
+
 impl PartialEq for Track {
     fn eq(&self, other: &Track) -> bool {
         other.id == self.id
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use serde_json::{self, Value};
+    use url::Url;
+
+    use client::Client;
+    use test_support::serve;
+    use pagination::Pages;
+    use super::*;
+
+    /// The JSON of the track in `tests/fixtures/track.json`.
+    pub const FIXTURE: &'static str = include_str!("../tests/fixtures/track.json");
+
+    /// Returns the track in `tests/fixtures/track.json`.
+    pub fn fixture() -> Track {
+        serde_json::from_str(FIXTURE).unwrap()
+    }
+
+    /// Returns the track in `tests/fixtures/track.json` as a JSON value, for building resources
+    /// that embed a track.
+    pub fn fixture_value() -> Value {
+        serde_json::from_str(FIXTURE).unwrap()
+    }
+
+    #[test]
+    fn test_liked_tracks_are_favorites() {
+        let addr = serve(|_, _| {
+            let body = format!(r#"{{"collection": [{}]}}"#, FIXTURE);
+
+            ("200 OK".to_owned(), body.into_bytes())
+        });
+        let client = Client::new("abc");
+        let url = Url::parse(&format!("http://{}/me/likes/tracks", addr)).unwrap();
+        let tracks = LikedTracks { pages: Pages::from_url(&client, url) }.all().unwrap();
+
+        assert_eq!(tracks.len(), 1);
+        assert_eq!(tracks[0].user_favorite, Some(true));
+    }
+}
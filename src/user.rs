@@ -0,0 +1,271 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use serde_json;
+use url::Url;
+
+use activity::{Activity, Feed};
+use client::{Client, Connection, User, WebProfile};
+use error::{Error, Result};
+use pagination::Pages;
+use playlist::Playlist;
+use repost::Repost;
+use track::Track;
+
+#[derive(Debug)]
+pub struct SingleUserRequestBuilder<'a> {
+    client: &'a Client,
+    pub id: usize,
+}
+
+impl<'a> SingleUserRequestBuilder<'a> {
+    /// Constructs a new user request.
+    pub fn new(client: &'a Client, id: usize) -> SingleUserRequestBuilder {
+        SingleUserRequestBuilder {
+            client: client,
+            id: id,
+        }
+    }
+
+    /// Sends the request and returns the user.
+    pub fn get(&mut self) -> Result<User> {
+        let no_params: Option<&[(&str, &str)]> = None;
+        let response = try!(self.client.get(&format!("/users/{}", self.id), no_params));
+        let user: User = try!(serde_json::from_reader(response));
+
+        Ok(user)
+    }
+
+    /// Returns an iterator over the pages of users following the user.
+    pub fn followers(&self) -> Pages<'a, User> {
+        Pages::new(self.client, &format!("/users/{}/followers", self.id), &[])
+    }
+
+    /// Returns an iterator over the pages of users followed by the user.
+    pub fn followings(&self) -> Pages<'a, User> {
+        Pages::new(self.client, &format!("/users/{}/followings", self.id), &[])
+    }
+
+    /// Returns an iterator over the pages of tracks uploaded by the user.
+    pub fn tracks(&self) -> Pages<'a, Track> {
+        Pages::new(self.client, &format!("/users/{}/tracks", self.id), &[])
+    }
+
+    /// Sends a request for the users links to profiles on other sites.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use soundcloud::Client;
+    ///
+    /// let client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+    ///
+    /// for profile in client.user(1234).web_profiles().unwrap() {
+    ///     println!("{}: {}", profile.service, profile.url);
+    /// }
+    /// ```
+    pub fn web_profiles(&self) -> Result<Vec<WebProfile>> {
+        let no_params: Option<&[(&str, &str)]> = None;
+        let path = format!("/users/{}/web-profiles", self.id);
+        let response = try!(self.client.get(&path, no_params));
+        let profiles: Vec<WebProfile> = try!(serde_json::from_reader(response));
+
+        Ok(profiles)
+    }
+
+    /// Returns an iterator over the pages of tracks and playlists reposted by the user.
+    pub fn reposts(&self) -> Pages<'a, Repost> {
+        Pages::new(self.client, &format!("/users/{}/reposts", self.id), &[])
+    }
+}
+
+/// Builder for requests about the authenticated user.
+///
+/// All requests require the client to have an access token. `get` checks for one before sending
+/// the request and returns `Error::NotAuthenticated` without it, while the iterators only send
+/// their requests once they're advanced, so without a token the first page is the error returned
+/// by the API.
+#[derive(Debug)]
+pub struct MeRequestBuilder<'a> {
+    client: &'a Client,
+}
+
+impl<'a> MeRequestBuilder<'a> {
+    /// Constructs a new request for the authenticated user.
+    pub fn new(client: &'a Client) -> MeRequestBuilder {
+        MeRequestBuilder {
+            client: client,
+        }
+    }
+
+    /// Sends the request and returns the authenticated user, including its private fields.
+    pub fn get(&mut self) -> Result<User> {
+        if self.client.access_token().is_none() {
+            return Err(Error::NotAuthenticated);
+        }
+
+        let no_params: Option<&[(&str, &str)]> = None;
+        let response = try!(self.client.get("/me", no_params));
+        let user: User = try!(serde_json::from_reader(response));
+
+        Ok(user)
+    }
+
+    /// Returns an iterator over the pages of tracks uploaded by the authenticated user, including
+    /// private tracks.
+    pub fn tracks(&self) -> Pages<'a, Track> {
+        Pages::new(self.client, "/me/tracks", &[])
+    }
+
+    /// Returns an iterator over the pages of playlists created by the authenticated user.
+    pub fn playlists(&self) -> Pages<'a, Playlist> {
+        Pages::new(self.client, "/me/playlists", &[])
+    }
+
+    /// Returns an iterator over the pages of users followed by the authenticated user.
+    pub fn followings(&self) -> Pages<'a, User> {
+        Pages::new(self.client, "/me/followings", &[])
+    }
+
+    /// Returns an iterator over the pages of the authenticated users activity feed.
+    pub fn activities(&self) -> Pages<'a, Activity> {
+        Pages::new(self.client, "/me/activities", &[])
+    }
+
+    /// Returns an iterator over the activities of the authenticated users feed, newest first.
+    pub fn feed(&self) -> Feed<'a> {
+        Feed::new(self.activities())
+    }
+
+    /// Returns the activities newer than the `cursor`, which is the `future_href` of a feed or of
+    /// a previous call, newest first.
+    ///
+    /// All pages of new activities are requested as the feed advances. Once its first page has
+    /// been fetched, the `future_href` of the returned feed is the cursor for the next poll. If
+    /// it's missing the same cursor should be polled again.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::thread;
+    /// use std::time::Duration;
+    /// use soundcloud::Client;
+    ///
+    /// let mut client = Client::new(env!("SOUNDCLOUD_CLIENT_ID"));
+    /// client.set_access_token(Some(env!("SOUNDCLOUD_ACCESS_TOKEN")));
+    ///
+    /// let mut feed = client.me().feed();
+    /// feed.next();
+    /// let mut cursor = feed.future_href().unwrap().to_owned();
+    ///
+    /// loop {
+    ///     let mut activities = client.me().activities_since(&cursor).unwrap();
+    ///
+    ///     for activity in activities.by_ref() {
+    ///         if let Some(track) = activity.unwrap().track() {
+    ///             println!("new track: {}", track.title);
+    ///         }
+    ///     }
+    ///
+    ///     cursor = activities.future_href().map(str::to_owned).unwrap_or(cursor);
+    ///     thread::sleep(Duration::from_secs(60));
+    /// }
+    /// ```
+    pub fn activities_since(&self, cursor: &str) -> Result<Feed<'a>> {
+        let url = try!(Url::parse(cursor).map_err(|_| Error::InvalidCursor(cursor.to_owned())));
+
+        Ok(Feed::new(Pages::from_url(self.client, url)))
+    }
+
+    /// Returns an iterator over the connections to external services of the authenticated user.
+    pub fn connections(&self) -> Pages<'a, Connection> {
+        Pages::new(self.client, "/me/connections", &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use client::{Client, Connection, User, WebProfile};
+    use test_support::{host, serve};
+    use track::tests::FIXTURE;
+
+    #[test]
+    fn test_activities_since_follows_next_href() {
+        let addr = serve(|path, headers| {
+            let host = host(headers);
+            let activity = format!(r#"{{
+                "type": "track",
+                "created_at": "2016/05/01 12:00:00 +0000",
+                "origin": {}
+            }}"#, FIXTURE);
+            let body = if path.starts_with("/activities?cursor=1") {
+                format!(r#"{{
+                    "collection": [{0}],
+                    "next_href": "http://{1}/activities?cursor=0",
+                    "future_href": "http://{1}/activities?cursor=2"
+                }}"#, activity, host)
+            } else {
+                format!(r#"{{"collection": [{}]}}"#, activity)
+            };
+
+            ("200 OK".to_owned(), body.into_bytes())
+        });
+        let client = Client::new("abc");
+        let cursor = format!("http://{}/activities?cursor=1", addr);
+        let mut activities = client.me().activities_since(&cursor).unwrap();
+
+        assert_eq!(activities.by_ref().map(Result::unwrap).count(), 2);
+        assert_eq!(activities.future_href(),
+                   Some(format!("http://{}/activities?cursor=2", addr).as_str()));
+        assert!(client.me().activities_since("not a url").is_err());
+    }
+
+    #[test]
+    fn test_parse_me() {
+        let user: User = serde_json::from_str(include_str!("../tests/fixtures/me.json")).unwrap();
+        let quota = user.quota.unwrap();
+
+        assert_eq!(user.plan, Some("Pro Unlimited".to_owned()));
+        assert_eq!(user.private_tracks_count, Some(3));
+        assert_eq!(user.private_playlists_count, Some(1));
+        assert_eq!(user.primary_email_confirmed, Some(true));
+        assert!(quota.unlimited_upload_quota);
+        assert_eq!(quota.upload_seconds_used, 12744);
+        assert_eq!(quota.upload_seconds_left, None);
+    }
+
+    #[test]
+    fn test_parse_connections() {
+        let connections: Vec<Connection> =
+            serde_json::from_str(include_str!("../tests/fixtures/connections.json")).unwrap();
+
+        assert_eq!(connections.len(), 2);
+        assert_eq!(connections[0].service, "twitter");
+        assert_eq!(connections[0].kind, Some("twitter".to_owned()));
+        assert!(connections[0].post_publish);
+        assert_eq!(connections[1].kind, None);
+        assert!(connections[1].post_favorite);
+    }
+
+    #[test]
+    fn test_parse_web_profiles() {
+        let profiles: Vec<WebProfile> =
+            serde_json::from_str(include_str!("../tests/fixtures/web_profiles.json")).unwrap();
+
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].service, "instagram");
+        assert_eq!(profiles[0].username, Some("isqamusic".to_owned()));
+        assert_eq!(profiles[0].title, Some("Instagram".to_owned()));
+        assert_eq!(profiles[1].url, "http://isqa.co.uk");
+        assert_eq!(profiles[1].username, None);
+        assert_eq!(profiles[1].title, None);
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cmp;
+
+/// Unicode block characters used to render a waveform, from lowest to highest.
+const BLOCKS: &'static [char] = &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Waveform data of a track.
+///
+/// SoundCloud renders waveforms as PNG images, but the same data is available as JSON on
+/// `wis.sndcdn.com`, where each sample is the height of a column in the image.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Waveform {
+    /// Number of samples.
+    pub width: u64,
+    /// Maximum sample value.
+    pub height: u64,
+    /// List of samples, between 0 and `height`.
+    pub samples: Vec<u64>,
+}
+
+impl Waveform {
+    /// Returns the samples as amplitudes between `0.0` and `1.0`.
+    pub fn normalized(&self) -> Vec<f32> {
+        if self.height == 0 {
+            return vec![0.0; self.samples.len()];
+        }
+
+        self.samples.iter()
+            .map(|&sample| (sample as f32 / self.height as f32).min(1.0))
+            .collect()
+    }
+
+    /// Returns the normalized amplitudes reduced to `bins` values, using the peak amplitude of
+    /// the samples that fall into each bin.
+    pub fn downsample(&self, bins: usize) -> Vec<f32> {
+        let amplitudes = self.normalized();
+        let len = amplitudes.len();
+
+        if len == 0 {
+            return vec![0.0; bins];
+        }
+
+        (0..bins)
+            .map(|bin| {
+                let start = cmp::min(bin * len / bins, len - 1);
+                let end = cmp::max((bin + 1) * len / bins, start + 1);
+
+                amplitudes[start..end].iter().cloned().fold(0.0, f32::max)
+            })
+            .collect()
+    }
+
+    /// Renders the waveform as a single line of `width` Unicode block characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use soundcloud::Waveform;
+    ///
+    /// let waveform = Waveform { width: 4, height: 8, samples: vec![1, 4, 8, 2] };
+    ///
+    /// assert_eq!(waveform.render_blocks(4), "▁▄█▂");
+    /// ```
+    pub fn render_blocks(&self, width: usize) -> String {
+        self.downsample(width).iter()
+            .map(|&amplitude| {
+                let index = (amplitude * BLOCKS.len() as f32).ceil() as usize;
+                BLOCKS[cmp::min(cmp::max(index, 1), BLOCKS.len()) - 1]
+            })
+            .collect()
+    }
+
+    /// Renders the waveform as `height` lines of `width` ASCII characters, mirrored around the
+    /// middle like the waveform on SoundCloud.com.
+    pub fn render_ascii(&self, width: usize, height: usize) -> String {
+        let half = height as f32 / 2.0;
+        let columns: Vec<f32> = self.downsample(width).iter()
+            .map(|&amplitude| amplitude * half)
+            .collect();
+        let mut lines = vec![];
+
+        for row in 0..height {
+            // Distance of the row's center from the middle of the waveform.
+            let distance = (row as f32 + 0.5 - half).abs();
+            let line: String = columns.iter()
+                .map(|&extent| if extent > 0.0 && distance <= extent { '#' } else { ' ' })
+                .collect();
+
+            lines.push(line.trim_right().to_owned());
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Returns the url of the JSON waveform data for the PNG `waveform_url` of a track.
+///
+/// # Examples
+///
+/// ```
+/// use soundcloud::waveform_json_url;
+///
+/// assert_eq!(waveform_json_url("https://w1.sndcdn.com/AbCdEfGhIjKl_m.png"),
+///            Some("https://wis.sndcdn.com/AbCdEfGhIjKl_m.json".to_owned()));
+/// ```
+pub fn waveform_json_url(waveform_url: &str) -> Option<String> {
+    if waveform_url.ends_with(".json") {
+        return Some(waveform_url.to_owned());
+    }
+
+    let name = match waveform_url.rfind('/') {
+        Some(pos) => &waveform_url[pos + 1..],
+        None => return None,
+    };
+
+    if !name.ends_with(".png") {
+        return None;
+    }
+
+    Some(format!("https://wis.sndcdn.com/{}.json", &name[..name.len() - 4]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn waveform() -> Waveform {
+        Waveform { width: 8, height: 100, samples: vec![0, 10, 50, 100, 100, 50, 20, 0] }
+    }
+
+    #[test]
+    fn test_downsample() {
+        assert_eq!(waveform().downsample(4), vec![0.1, 1.0, 1.0, 0.2]);
+        assert_eq!(waveform().downsample(16).len(), 16);
+        assert_eq!(waveform().downsample(16)[6], 1.0);
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        let waveform = Waveform { width: 3, height: 10, samples: vec![10, 5, 0] };
+
+        assert_eq!(waveform.render_ascii(3, 4), "#\n##\n##\n#");
+    }
+}
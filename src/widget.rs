@@ -0,0 +1,213 @@
+// Copyright (c) 2016, Mikkel Kroman <mk@uplink.io>
+// All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::time::Duration;
+
+use url::form_urlencoded;
+
+use client::User;
+use playlist::Playlist;
+use track::Track;
+
+/// Url of the embeddable widget player.
+pub const WIDGET_URL: &'static str = "https://w.soundcloud.com/player/";
+
+/// Height in pixels of the classic player for a single track.
+pub const TRACK_HEIGHT: u32 = 166;
+
+/// Height in pixels of the visual player, and of the player for playlists and users.
+pub const LIST_HEIGHT: u32 = 450;
+
+/// Builder of embeddable widget player urls and iframes, without any requests to the API.
+///
+/// # Examples
+///
+/// ```
+/// use soundcloud::Widget;
+///
+/// let widget = Widget::new("https://api.soundcloud.com/tracks/262681089").auto_play(true).url();
+///
+/// assert_eq!(widget, "https://w.soundcloud.com/player/\
+///                     ?url=https%3A%2F%2Fapi.soundcloud.com%2Ftracks%2F262681089&auto_play=true");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Widget {
+    uri: String,
+    height: u32,
+    color: Option<String>,
+    auto_play: Option<bool>,
+    hide_related: Option<bool>,
+    visual: Option<bool>,
+    start_time: Option<Duration>,
+}
+
+impl Widget {
+    /// Constructs a widget for the API resource `uri`, sized for a single track.
+    pub fn new(uri: &str) -> Widget {
+        Widget {
+            uri: uri.to_owned(),
+            height: TRACK_HEIGHT,
+            color: None,
+            auto_play: None,
+            hide_related: None,
+            visual: None,
+            start_time: None,
+        }
+    }
+
+    /// Constructs a widget for the `track`.
+    pub fn for_track(track: &Track) -> Widget {
+        Widget::new(&track.uri)
+    }
+
+    /// Constructs a widget for the tracks of the `user`.
+    pub fn for_user(user: &User) -> Widget {
+        let mut widget = Widget::new(&user.uri);
+        widget.height = LIST_HEIGHT;
+        widget
+    }
+
+    /// Constructs a widget for the `playlist`.
+    pub fn for_playlist(playlist: &Playlist) -> Widget {
+        let mut widget = Widget::new(&playlist.uri);
+        widget.height = LIST_HEIGHT;
+        widget
+    }
+
+    /// Sets the primary color of the player. A leading `#` is removed.
+    pub fn color<S: AsRef<str>>(&mut self, color: S) -> &mut Widget {
+        self.color = Some(color.as_ref().trim_left_matches('#').to_owned());
+        self
+    }
+
+    /// Sets whether the player starts playing on load.
+    pub fn auto_play(&mut self, auto_play: bool) -> &mut Widget {
+        self.auto_play = Some(auto_play);
+        self
+    }
+
+    /// Sets whether related tracks are hidden after playback ends.
+    pub fn hide_related(&mut self, hide_related: bool) -> &mut Widget {
+        self.hide_related = Some(hide_related);
+        self
+    }
+
+    /// Sets whether the large artwork player is used. This also sets the height of the iframe.
+    pub fn visual(&mut self, visual: bool) -> &mut Widget {
+        self.visual = Some(visual);
+
+        if visual {
+            self.height = LIST_HEIGHT;
+        }
+
+        self
+    }
+
+    /// Sets the position playback starts at. Only whole seconds are used.
+    pub fn start_time(&mut self, start_time: Duration) -> &mut Widget {
+        self.start_time = Some(start_time);
+        self
+    }
+
+    /// Sets the height of the iframe in pixels.
+    pub fn height(&mut self, height: u32) -> &mut Widget {
+        self.height = height;
+        self
+    }
+
+    /// Returns the url of the widget player.
+    pub fn url(&self) -> String {
+        let mut uri = self.uri.clone();
+
+        if let Some(start_time) = self.start_time {
+            let seconds = start_time.as_secs();
+            uri.push_str(&format!("#t={}m{}s", seconds / 60, seconds % 60));
+        }
+
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        query.append_pair("url", &uri);
+
+        if let Some(ref color) = self.color {
+            query.append_pair("color", color);
+        }
+
+        if let Some(auto_play) = self.auto_play {
+            query.append_pair("auto_play", &auto_play.to_string());
+        }
+
+        if let Some(hide_related) = self.hide_related {
+            query.append_pair("hide_related", &hide_related.to_string());
+        }
+
+        if let Some(visual) = self.visual {
+            query.append_pair("visual", &visual.to_string());
+        }
+
+        format!("{}?{}", WIDGET_URL, query.finish())
+    }
+
+    /// Returns the HTML iframe of the widget player.
+    pub fn html(&self) -> String {
+        format!("<iframe width=\"100%\" height=\"{}\" scrolling=\"no\" frameborder=\"no\" \
+                 src=\"{}\"></iframe>",
+                self.height,
+                escape(&self.url()))
+    }
+}
+
+/// Escapes `s` for use in an HTML attribute value.
+fn escape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use track::tests::fixture;
+
+    #[test]
+    fn test_track_url() {
+        let url = Widget::for_track(&fixture())
+            .color("#ff5500")
+            .hide_related(true)
+            .start_time(Duration::from_secs(90))
+            .url();
+
+        assert_eq!(url,
+                   "https://w.soundcloud.com/player/\
+                    ?url=https%3A%2F%2Fapi.soundcloud.com%2Ftracks%2F262681089%23t%3D1m30s\
+                    &color=ff5500&hide_related=true");
+    }
+
+    #[test]
+    fn test_html_escapes_uri() {
+        let html = Widget::new("https://api.soundcloud.com/tracks/1?a=\"b\"&c").visual(true).html();
+
+        assert_eq!(html,
+                   "<iframe width=\"100%\" height=\"450\" scrolling=\"no\" frameborder=\"no\" \
+                    src=\"https://w.soundcloud.com/player/\
+                    ?url=https%3A%2F%2Fapi.soundcloud.com%2Ftracks%2F1%3Fa%3D%22b%22%26c\
+                    &amp;visual=true\"></iframe>");
+    }
+}